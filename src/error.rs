@@ -17,6 +17,15 @@ pub enum TreeError {
     NoDataAllowed( usize ),
     NotAncestorOf( usize, usize, Box<TreeError> ),
     IsAncestorOf( usize, usize ),
+    HasData( usize ),
+    DowncastFailed( usize ),
+    AmbiguousRoot( usize ),
+    InvalidEdge( usize ),
+    NoDataFound( usize, usize ),
+    NotAPermutation( usize ),
+    CycleDetected( usize ),
+    MoveRejected( usize, usize ),
+    NoRootNode,
 }
 
 impl Display for TreeError {
@@ -48,6 +57,24 @@ impl Display for TreeError {
                 ),
             TreeError::IsAncestorOf( index,is_ancestor ) =>
                 write!( formatter, "The node {} is an ancestor of the node {}.", is_ancestor, index, ),
+            TreeError::HasData( index ) =>
+                write!( formatter, "Can't disallow data for the node {} as it still has data.", index ),
+            TreeError::DowncastFailed( index ) =>
+                write!( formatter, "Failed to downcast to the requested type for the node {}.", index ),
+            TreeError::AmbiguousRoot( count ) =>
+                write!( formatter, "Found {} root nodes, where exactly 1 root node is required.", count ),
+            TreeError::InvalidEdge( index ) =>
+                write!( formatter, "The edge for the node {} is invalid.", index ),
+            TreeError::NoDataFound( position, index ) =>
+                write!( formatter, "No data was found at position {} for the node {}.", position, index ),
+            TreeError::NotAPermutation( index ) =>
+                write!( formatter, "The new order is not a permutation of the children of the node {}.", index ),
+            TreeError::CycleDetected( index ) =>
+                write!( formatter, "A cycle was detected in the parents of the node {}.", index ),
+            TreeError::MoveRejected( index, parent ) =>
+                write!( formatter, "The move of the node {} to the parent {} was rejected.", index, parent ),
+            TreeError::NoRootNode =>
+                write!( formatter, "The tree has no root node." ),
         }
     }
 }
@@ -3,6 +3,8 @@
 
 use crate::TreeError;
 use core::any::Any;
+use core::cmp::Ordering;
+use std::collections::{ BTreeMap, HashMap };
 
 /// Indicates that the node can have children.
 /// 
@@ -23,7 +25,10 @@ pub const ALLOW_DATA: u8 = 0b00000010;
 /// See the crate's information page for details regarding the struct.
 pub struct Tree {
     nodes: Vec<Option<Node>>,
-    root: Option<usize>
+    root: Option<usize>,
+
+    // The optional callback set by `set_on_mutation`, which is notified of the structural and data mutations.
+    on_mutation: Option<Box<dyn FnMut( Mutation )>>,
 }
 
 impl Tree {
@@ -44,8 +49,130 @@ impl Tree {
     pub fn new() -> Self {
         Tree {
             nodes: Vec::new(),
-            root: None
+            root: None,
+            on_mutation: None,
+        }
+    }
+
+    /// Create a new tree with the nested structure of the `shape`, as obtained from [`to_shape`].
+    /// 
+    /// The nodes are created in pre-order, have no node types and data types, and have empty data when [`ALLOW_DATA`]
+    /// is set. The children of a node without [`ALLOW_CHILDREN`] are ignored.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA, TreeShape};
+    /// 
+    /// let shape = TreeShape::Node {
+    ///     features: ALLOW_CHILDREN,
+    ///     children: vec![ TreeShape::Node { features: ALLOW_DATA, children: Vec::new() } ],
+    /// };
+    /// let tree = Tree::from_shape( &shape );
+    /// assert_eq!( tree.count(), 2, "2 nodes are present." );
+    /// assert!( tree.data_ref( 1 ).unwrap().is_empty() );
+    /// assert_eq!( tree.to_shape(), Some( shape ) );
+    /// ```
+    /// 
+    /// [`to_shape`]: Tree::to_shape
+    /// [`ALLOW_DATA`]: ALLOW_DATA
+    /// [`ALLOW_CHILDREN`]: ALLOW_CHILDREN
+    pub fn from_shape( shape: &TreeShape ) -> Tree {
+        let mut tree = Tree::new();
+        let mut stack = vec![ ( shape, 0 ) ];
+        while let Some( ( TreeShape::Node { features, children }, parent ) ) = stack.pop() {
+            let index = tree.insert( parent, *features, None, None ).unwrap();
+            if features & ALLOW_CHILDREN == ALLOW_CHILDREN {
+                stack.extend( children.iter().rev().map( |child| ( child, index ) ) );
+            }
         }
+        tree
+    }
+
+    /// Set the callback `hook` to be notified of the mutations of the tree, replacing any previously set callback.
+    /// 
+    /// The `hook` is called with a [`Mutation`] for each affected node once the mutation has been made, except for
+    /// [`Mutation::Data`], which is notified when mutable access to the data of a node is obtained, before any change
+    /// is made through it. The `hook` is called from the methods:
+    /// 
+    /// - [`insert`], [`insert_at`] and [`wrap`] with [`Mutation::Insert`],
+    /// - [`delete`], [`take`], [`collapse`], [`clear`], [`remove_unreachable`] and [`split_off`] with
+    ///   [`Mutation::Delete`], where every node of a removed subtree is notified,
+    /// - [`reparent`], [`swap_subtrees`], [`flatten_children`], [`collapse`] and [`wrap`] with [`Mutation::Move`] for
+    ///   every node given a new parent or position,
+    /// - [`set_child_order`], [`swap_children`] and [`repair_children`] with [`Mutation::Children`],
+    /// - [`set_features`], [`replace_node_in_place`], [`take_node_type`] and [`take_data_type`] with
+    ///   [`Mutation::Update`],
+    /// - [`data_mut`], [`clear_subtree_data`], [`clear_all_data`], [`each_data_mut`] and [`take_all_of_type`] with
+    ///   [`Mutation::Data`].
+    /// 
+    /// All the other methods that mutate the tree are built on these methods, thus also notify the `hook` (such as
+    /// [`move_nodes`], [`bulk_delete`], [`trim_to_depth`], [`graft_many`] and [`data_retain`]). Only [`rebuild_root`],
+    /// which sets the root node without changing any node, does not notify the `hook`. Useful for invalidating caches
+    /// derived from the tree.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA, Mutation};
+    /// use std::rc::Rc;
+    /// use std::cell::RefCell;
+    /// 
+    /// let events = Rc::new( RefCell::new( Vec::<Mutation>::new() ) );
+    /// let mut tree = Tree::new();
+    /// let recorder = Rc::clone( &events );
+    /// tree.set_on_mutation( move |mutation| recorder.borrow_mut().push( mutation ) );
+    /// tree.insert( 297, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// tree.delete( 1 ).ok();
+    /// assert_eq!(
+    ///     *events.borrow(),
+    ///     vec![ Mutation::Insert( 0 ), Mutation::Insert( 1 ), Mutation::Delete( 1 ) ]
+    /// );
+    /// ```
+    /// 
+    /// [`Mutation`]: Mutation
+    /// [`Mutation::Data`]: Mutation::Data
+    /// [`insert`]: Tree::insert
+    /// [`insert_at`]: Tree::insert_at
+    /// [`wrap`]: Tree::wrap
+    /// [`Mutation::Insert`]: Mutation::Insert
+    /// [`delete`]: Tree::delete
+    /// [`take`]: Tree::take
+    /// [`collapse`]: Tree::collapse
+    /// [`clear`]: Tree::clear
+    /// [`remove_unreachable`]: Tree::remove_unreachable
+    /// [`split_off`]: Tree::split_off
+    /// [`Mutation::Delete`]: Mutation::Delete
+    /// [`reparent`]: Tree::reparent
+    /// [`swap_subtrees`]: Tree::swap_subtrees
+    /// [`flatten_children`]: Tree::flatten_children
+    /// [`Mutation::Move`]: Mutation::Move
+    /// [`set_child_order`]: Tree::set_child_order
+    /// [`swap_children`]: Tree::swap_children
+    /// [`repair_children`]: Tree::repair_children
+    /// [`Mutation::Children`]: Mutation::Children
+    /// [`set_features`]: Tree::set_features
+    /// [`replace_node_in_place`]: Tree::replace_node_in_place
+    /// [`take_node_type`]: Tree::take_node_type
+    /// [`take_data_type`]: Tree::take_data_type
+    /// [`Mutation::Update`]: Mutation::Update
+    /// [`data_mut`]: Tree::data_mut
+    /// [`clear_subtree_data`]: Tree::clear_subtree_data
+    /// [`clear_all_data`]: Tree::clear_all_data
+    /// [`each_data_mut`]: Tree::each_data_mut
+    /// [`take_all_of_type`]: Tree::take_all_of_type
+    /// [`move_nodes`]: Tree::move_nodes
+    /// [`bulk_delete`]: Tree::bulk_delete
+    /// [`trim_to_depth`]: Tree::trim_to_depth
+    /// [`graft_many`]: Tree::graft_many
+    /// [`data_retain`]: Tree::data_retain
+    /// [`rebuild_root`]: Tree::rebuild_root
+    pub fn set_on_mutation<F>( &mut self, hook: F )
+    where
+        F: FnMut( Mutation ) + 'static
+    {
+        self.on_mutation = Some( Box::new( hook ) );
     }
 
     /// Create a node, and append it to the end of the `node_index` node's children.
@@ -98,7 +225,7 @@ impl Tree {
         let mut data = None;
 
         // `node_index` is ignored when first node is inserted into tree.
-        if !self.root.is_none() {
+        if self.root.is_some() {
             let Some( index_node ) = self.node( node_index ) else {
                 return Err( TreeError::RetrievingNode( node_index ) )
             };
@@ -121,16 +248,11 @@ impl Tree {
             data,
             data_type,
         } );
-        let mut _index = 0;
-        match self.nodes.iter().position( |x| x.is_none() ) {
-            None => {
-                _index = self.nodes.len();
-                self.nodes.push( node );
-            },
-            Some( position ) => {
-                _index = position;
-                *self.nodes.get_mut( position ).unwrap() = node; 
-            }
+        let _index = self.next_insert_index();
+        if _index == self.nodes.len() {
+            self.nodes.push( node );
+        } else {
+            *self.nodes.get_mut( _index ).unwrap() = node;
         }
         if self.root.is_none() {
             self.root = Some( _index );
@@ -140,6 +262,7 @@ impl Tree {
             };
             index_node.children.as_mut().unwrap().push( _index );
         }
+        self.notify( Mutation::Insert( _index ) );
         Ok( _index )
     }
 
@@ -174,7 +297,7 @@ impl Tree {
         let mut data = None;
 
         // `node_index` is ignored when first node is inserted into tree.
-        if !self.root.is_none() {
+        if self.root.is_some() {
             let Some( index_node ) = self.node( node_index ) else {
                 return Err( TreeError::RetrievingNode( node_index ) )
             };
@@ -200,16 +323,11 @@ impl Tree {
             data,
             data_type,
         } );
-        let mut _index = 0;
-        match self.nodes.iter().position( |x| x.is_none() ) {
-            None => {
-                _index = self.nodes.len();
-                self.nodes.push( node );
-            },
-            Some( position ) => {
-                _index = position;
-                *self.nodes.get_mut( position ).unwrap() = node; 
-            }
+        let _index = self.next_insert_index();
+        if _index == self.nodes.len() {
+            self.nodes.push( node );
+        } else {
+            *self.nodes.get_mut( _index ).unwrap() = node;
         }
         if self.root.is_none() {
             self.root = Some( _index );
@@ -219,16 +337,16 @@ impl Tree {
             };
             index_node.children.as_mut().unwrap().insert( position, _index );
         }
+        self.notify( Mutation::Insert( _index ) );
         Ok( _index )
     }
 
-    /// Deletes the specified node `node_index` from the tree.
-    /// 
-    /// # WARNING
-    /// 
-    /// This is a destructive method that destroys the data when deleting the node.
+    /// Create a node with the `data`, and insert it into the tree as the last child of the specified node
+    /// `node_index`, as [`insert`] does.
     /// 
-    /// If wanting the data, use [`take`] method instead.
+    /// The `data` is moved into the node when the `features` includes [`ALLOW_DATA`]. When the `features` does not
+    /// include [`ALLOW_DATA`] the `data` must be empty, else the error [`TreeError::NoDataAllowed`] is returned with
+    /// the index the node would have had, and no node is created.
     /// 
     /// # Examples
     /// 
@@ -236,53 +354,41 @@ impl Tree {
     /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
     /// 
     /// let mut tree = Tree::new();
-    /// tree.insert( 68, ALLOW_CHILDREN, None, None ).ok();
-    /// assert_eq!( tree.count(), 1, "1 node is present." );
-    /// match tree.delete( 0 ) {
-    ///     Err( error ) => println!( "{}", error ),
-    ///     Ok( _ ) => println!( "Succeeded to delete node." )
-    /// }
-    /// assert_eq!( tree.count(), 0, "0 nodes are present." );
+    /// tree.insert( 425, ALLOW_CHILDREN, None, None ).ok();
+    /// let index = tree.insert_with_data( 0, ALLOW_DATA, None, None, vec![ Box::new( "x".to_string() ) ] ).unwrap();
+    /// assert_eq!( tree.data_ref( index ).unwrap().len(), 1 );
     /// ```
     /// 
-    /// [`take`]: Tree::take
-    pub fn delete( &mut self, node_index: usize ) -> Result<(), TreeError> {
-        let mut _parent = None;
-        {
-            let Some( index_node ) = self.node( node_index ) else {
-                return Err( TreeError::RetrievingNode( node_index ) )
-            };
-            if
-                index_node.features & ALLOW_CHILDREN == ALLOW_CHILDREN
-                && !index_node.children.as_ref().unwrap().is_empty()
-            {
-                return Err( TreeError::HasChildren( node_index ) );
-            }
-            _parent = index_node.parent;
-        }
-        {
-            if !_parent.is_none() {
-                let parent = _parent.unwrap();
-                let Some( parent_node ) = self.node_mut( parent ) else {
-                    return Err( TreeError::RetrievingNode( parent ) )
-                };
-                let children = parent_node.children.as_mut().unwrap();
-                let Some( _position ) = children.iter().position( |&x| x == node_index ) else {
-                    return Err( TreeError::MissingInParent( node_index, parent ) ); // Serious integrity issue.
-                };
-                children.remove( _position );
-            }
+    /// [`insert`]: Tree::insert
+    /// [`ALLOW_DATA`]: ALLOW_DATA
+    /// [`TreeError::NoDataAllowed`]: TreeError::NoDataAllowed
+    pub fn insert_with_data(
+        &mut self,
+        node_index: usize,
+        features: u8,
+        node_type: Option<Box<dyn Any>>,
+        data_type: Option<Box<dyn Any>>,
+        data: Vec<Box<dyn Any>>,
+    ) -> Result<usize, TreeError> {
+        let allow_data = features & ALLOW_DATA == ALLOW_DATA;
+        if !allow_data && !data.is_empty() {
+            return Err( TreeError::NoDataAllowed( self.next_insert_index() ) );
         }
-        let mut _node_ref = self.nodes.get_mut( node_index ).unwrap();
-        *_node_ref = None;
-        if Some( node_index ) == self.root {
-            self.root = None;
-            self.nodes.clear();
+        let index = self.insert( node_index, features, node_type, data_type )?;
+        if allow_data {
+            *self.data_mut( index )? = data;
         }
-        Ok( () )
+        Ok( index )
     }
 
-    /// Deletes the specified node `node_index` from the tree, and return its data (if any).
+    /// Create a node, and insert it between the specified node `node_index` and its parent. The created node takes the
+    /// place of `node_index` in the parent's children, and `node_index` becomes the only child of the created node. If
+    /// `node_index` is the root node, then the created node becomes the new root node.
+    /// 
+    /// See [`insert`] for details of the parameters. The `features` must include [`ALLOW_CHILDREN`], else the error
+    /// [`TreeError::NoChildrenAllowed`] is returned for `node_index`, as the created node can't have it as a child.
+    /// 
+    /// If no error, the returned [`usize`] value is the index of the created node in the tree.
     /// 
     /// # Examples
     /// 
@@ -290,56 +396,73 @@ impl Tree {
     /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
     /// 
     /// let mut tree = Tree::new();
-    /// tree.insert( 128, ALLOW_CHILDREN | ALLOW_DATA, None, None ).ok();
-    /// tree.data_mut( 0 ).unwrap().push( Box::new( "String data".to_string() ) );
-    /// assert_eq!( tree.count(), 1, "1 node is present." );
-    /// let mut data_vec = tree.take( 0 ).ok().unwrap().unwrap(); // Deleting root node, and take data.
-    /// let data = data_vec.pop().unwrap().downcast::<String>().ok().unwrap();
-    /// assert_eq!( tree.count(), 0, "0 nodes are present." );
-    /// assert_eq!( *data, "String data".to_string(), "Data of node is a string" );
+    /// tree.insert( 4, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// let index = tree.wrap( 1, ALLOW_CHILDREN, None, None ).unwrap();
+    /// assert_eq!( tree.parent( 1 ).unwrap(), index, "Node 1 is wrapped." );
+    /// assert_eq!( tree.parent( index ).unwrap(), 0, "Parent of wrapping node is root." );
     /// ```
-    pub fn take( &mut self, node_index: usize ) -> Result<Option<Vec<Box<dyn Any>>>, TreeError> {
-        let mut _parent = None;
-        {
-            let Some( index_node ) = self.node( node_index ) else {
-                return Err( TreeError::RetrievingNode( node_index ) )
+    /// 
+    /// [`insert`]: Tree::insert
+    /// [`ALLOW_CHILDREN`]: ALLOW_CHILDREN
+    /// [`TreeError::NoChildrenAllowed`]: TreeError::NoChildrenAllowed
+    /// [`usize`]: usize
+    pub fn wrap(
+        &mut self,
+        node_index: usize,
+        features: u8,
+        node_type: Option<Box<dyn Any>>,
+        data_type: Option<Box<dyn Any>>,
+    ) -> Result<usize, TreeError> {
+        let Some( index_node ) = self.node( node_index ) else {
+            return Err( TreeError::RetrievingNode( node_index ) )
+        };
+        if features & ALLOW_CHILDREN != ALLOW_CHILDREN {
+            return Err( TreeError::NoChildrenAllowed( node_index ) );
+        }
+        let parent = index_node.parent;
+        let index = self.next_insert_index();
+        let mut position = None;
+        if let Some( parent ) = parent {
+            let Some( _position ) = self.children( parent )?.iter().position( |&x| x == node_index ) else {
+                return Err( TreeError::MissingInParent( node_index, parent ) ); // Serious integrity issue.
             };
-            if
-                index_node.features & ALLOW_CHILDREN == ALLOW_CHILDREN
-                && !index_node.children.as_ref().unwrap().is_empty()
-            {
-                return Err( TreeError::HasChildren( node_index ) );
-            }
-            _parent = index_node.parent;
+            position = Some( _position );
         }
-        {
-            if !_parent.is_none() {
-                let parent = _parent.unwrap();
-                let Some( parent_node ) = self.node_mut( parent ) else {
-                    return Err( TreeError::RetrievingNode( parent ) )
-                };
-                let children = parent_node.children.as_mut().unwrap();
-                let Some( _position ) = children.iter().position( |&x| x == node_index ) else {
-                    return Err( TreeError::MissingInParent( node_index, parent ) ); // Serious integrity issue.
-                };
-                children.remove( _position );
-            }
+        let mut data = None;
+        if features & ALLOW_DATA == ALLOW_DATA {
+            data = Some( Vec::<Box<dyn Any>>::new() );
         }
-        let mut _node_ref = self.nodes.get_mut( node_index ).unwrap();
-        let node = _node_ref.take().unwrap();
-        *_node_ref = None;
-        if Some( node_index ) == self.root {
-            self.root = None;
-            self.nodes.clear();
+        let node = Some( Node {
+            node_type,
+            features,
+            parent,
+            children: Some( vec![ node_index ] ),
+            data,
+            data_type,
+        } );
+        if index == self.nodes.len() {
+            self.nodes.push( node );
+        } else {
+            *self.nodes.get_mut( index ).unwrap() = node;
         }
-        Ok( node.data )
+        match parent {
+            Some( parent ) => {
+                self.node_mut( parent ).unwrap().children.as_mut().unwrap()[ position.unwrap() ] = index;
+            },
+            None => self.root = Some( index ),
+        }
+        self.node_mut( node_index ).unwrap().parent = Some( index );
+        self.notify( Mutation::Insert( index ) );
+        self.notify( Mutation::Move( node_index ) );
+        Ok( index )
     }
 
-    /// Clear the tree of all nodes.
+    /// Create the root node if the tree is empty, else nothing is done and the parameters are discarded (ignored).
     /// 
-    /// # WARNING
+    /// See [`insert`] for details of the parameters.
     /// 
-    /// All data in the nodes will be destroyed.
+    /// The returned [`usize`] value is the index of the root node, either newly created or already existing.
     /// 
     /// # Examples
     /// 
@@ -347,154 +470,171 @@ impl Tree {
     /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
     /// 
     /// let mut tree = Tree::new();
-    /// tree.insert( 254, ALLOW_CHILDREN, None, None ).ok();
-    /// assert_eq!( tree.count(), 1, "1 node is present." );
-    /// tree.clear();
-    /// assert_eq!( tree.count(), 0, "0 nodes are present." );
+    /// let root = tree.insert_root_if_empty( ALLOW_CHILDREN, None, None );
+    /// assert_eq!( root, 0, "Root node is index 0." );
+    /// tree.insert( root, ALLOW_DATA, None, None ).ok();
+    /// assert_eq!( tree.insert_root_if_empty( ALLOW_CHILDREN, None, None ), root, "Existing root node." );
+    /// assert_eq!( tree.count(), 2, "2 nodes are present." );
     /// ```
-    pub fn clear( &mut self ) {
-        self.root = None;
-        self.nodes.clear();
+    /// 
+    /// [`insert`]: Tree::insert
+    /// [`usize`]: usize
+    pub fn insert_root_if_empty(
+        &mut self,
+        features: u8,
+        node_type: Option<Box<dyn Any>>,
+        data_type: Option<Box<dyn Any>>,
+    ) -> usize {
+        if let Some( root ) = self.root {
+            return root;
+        }
+
+        // Inserting into an empty tree can't fail, as `node_index` is ignored.
+        self.insert( 0, features, node_type, data_type ).unwrap()
     }
 
-    /// Move part of the tree from one position to another within the tree.
+    /// Create a subtree described by `edges`, and append the subtree's root node to the end of the `parent` node's
+    /// children.
     /// 
-    /// The `destination` node must be able to have children, else move will not occur. Also the `source` node can't
-    /// already be an ancestor of `destination` node.
+    /// Each edge is a tuple of a local node identifier and the local identifier of its parent, where the subtree's root
+    /// node has the parent `None`. The local identifiers are only used to describe the subtree, and are not the indices
+    /// of the created nodes. The children of each node are created in the order they appear in `edges`. All the
+    /// created nodes have the specified `features`, and have no node type or data type.
     /// 
-    /// Parameter `position` is optional, and when passed as `None` the position is taken to be the last child of the
-    /// `destination` node.
+    /// The `edges` must contain exactly one root node, else [`TreeError::AmbiguousRoot`] is returned. A local
+    /// identifier that is repeated, refers to a missing parent, is not connected to the root node, or has a parent while
+    /// the `features` don't allow children results in [`TreeError::InvalidEdge`] containing the local identifier. The
+    /// `edges` are validated before any node is created.
+    /// 
+    /// If no error, the returned [`usize`] value is the index of the subtree's root node in the tree, which is the only
+    /// mapping from the local identifiers to the indices of the created nodes. The nodes are created in pre-order,
+    /// thus the indices of the created nodes can be obtained from the returned index with [`dfs_order`].
     /// 
     /// # Examples
     /// 
     /// ```
-    /// use tree::{ Tree, ALLOW_CHILDREN, ALLOW_DATA, TreeError };
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
     /// 
     /// let mut tree = Tree::new();
-    /// tree.insert( 338, ALLOW_CHILDREN, None, None, ).ok();
-    /// tree.insert( 0, ALLOW_CHILDREN, None, None, ).ok();
-    /// tree.insert( 0, ALLOW_CHILDREN, None, None, ).ok();
-    /// tree.insert( 1, ALLOW_CHILDREN, None, None, ).ok().unwrap();
-    /// tree.insert( 3, ALLOW_CHILDREN, None, None, ).ok();
-    /// assert_eq!( tree.parent( 3 ).unwrap(), 1, "Parent of node 3 must be 1." );
-    /// tree.move_nodes( 3, 2, None ).ok();
-    /// assert_eq!( tree.parent( 3 ).unwrap(), 1, "Parent of node 3 must be 2." );
-    /// assert_eq!( tree.parent( 4 ).unwrap(), 3, "Parent of node 4 must be 3." );
+    /// tree.insert( 4, ALLOW_CHILDREN, None, None ).ok();
+    /// let edges = [ ( 10, None ), ( 11, Some( 10 ) ), ( 12, Some( 10 ) ) ];
+    /// let root = tree.insert_subtree_from_edges( 0, &edges, ALLOW_CHILDREN | ALLOW_DATA ).unwrap();
+    /// assert_eq!( tree.count(), 4, "4 nodes are present." );
+    /// assert_eq!( tree.parent( root ).unwrap(), 0, "Subtree is a child of root." );
+    /// assert_eq!( tree.children( root ).unwrap().len(), 2, "Subtree root has 2 children." );
     /// ```
-    pub fn move_nodes(
+    /// 
+    /// [`TreeError::AmbiguousRoot`]: TreeError::AmbiguousRoot
+    /// [`TreeError::InvalidEdge`]: TreeError::InvalidEdge
+    /// [`usize`]: usize
+    /// [`dfs_order`]: Tree::dfs_order
+    pub fn insert_subtree_from_edges(
         &mut self,
-        source: usize,
-        destination: usize,
-        position: Option<usize>
-    ) -> Result<(), TreeError> {
-        
-        // Check if destination allows for children.
-        {
-            let Some( node ) = self.node( destination ) else {
-                return Err( TreeError::RetrievingNode( destination ) )
-            };
-            if node.features & ALLOW_CHILDREN != ALLOW_CHILDREN {
-                return Err( TreeError::NoChildrenAllowed( destination ) );
-            }
+        parent: usize,
+        edges: &[( usize, Option<usize> )],
+        features: u8,
+    ) -> Result<usize, TreeError> {
+        self.children( parent )?;
+        let roots = edges.iter().filter( |( _, local_parent )| local_parent.is_none() ).count();
+        if roots != 1 {
+            return Err( TreeError::AmbiguousRoot( roots ) );
         }
-
-        // Check that source is not an ancestor to destination.
-        match self.is_ancestor_of( destination, source ) {
-            Ok( _ ) => return Err( TreeError::IsAncestorOf( source, destination ) ),
-            Err( _ ) => {}
-        };
-        let mut _parent = None;
-        {
-            let Some( index_node ) = self.node( source ) else {
-                return Err( TreeError::RetrievingNode( source ) )
-            };
-            _parent = index_node.parent;
+        let mut children = HashMap::<usize, Vec<usize>>::new();
+        for ( local, local_parent ) in edges {
+            if children.insert( *local, Vec::<usize>::new() ).is_some() {
+                return Err( TreeError::InvalidEdge( *local ) );
+            }
+            if local_parent.is_some() && features & ALLOW_CHILDREN != ALLOW_CHILDREN {
+                return Err( TreeError::InvalidEdge( *local ) );
+            }
         }
-        let parent = _parent.unwrap();
-
-        // Check if source is already a child of destination, if so just a position change in destination's children.
-        if parent == destination {
-            let Some( node ) = self.node_mut( destination ) else {
-                return Err( TreeError::RetrievingNode( destination ) )
-            };
-            let children = node.children.as_mut().unwrap();
-            let Some( source_position ) = children.iter().position( |&x| x == source ) else {
-                return Err( TreeError::MissingInParent( source, destination ) ); // Serious integrity issue.
-            };
-            let destination_position = match position {
-                Some( value ) => value,
-                None => children.len() - 1
-            };
-            if source_position == destination_position {
-                // Nothing to do.
-            } else if destination_position < source_position {
-                children.remove( source_position );
-                children.insert( destination_position, source );
-            } else {
-                children.insert( destination_position, source );
-                children.remove( source_position );
+        let mut local_root = 0;
+        for ( local, local_parent ) in edges {
+            match local_parent {
+                None => local_root = *local,
+                Some( local_parent ) => match children.get_mut( local_parent ) {
+                    Some( siblings ) => siblings.push( *local ),
+                    None => return Err( TreeError::InvalidEdge( *local ) ),
+                }
             }
-            return Ok( () )
         }
 
-        // Remove source from source's parent's children.
-        {
-            let Some( node ) = self.node_mut( parent ) else {
-                return Err( TreeError::RetrievingNode( parent ) )
-            };
-            let children = node.children.as_mut().unwrap();
-            let Some( _position ) = children.iter().position( |&x| x == source ) else {
-                // Serious integrity issue.
-                return Err( TreeError::MissingInParent( source, parent ) );
-            };
-            children.remove( _position );
+        // Determine the creation order, and ensure every node is connected to the root node.
+        let mut order = Vec::<( usize, Option<usize> )>::new();
+        let mut stack = vec![ ( local_root, None ) ];
+        while let Some( ( local, local_parent ) ) = stack.pop() {
+            order.push( ( local, local_parent ) );
+            stack.extend( children[ &local ].iter().rev().map( |&child| ( child, Some( local ) ) ) );
         }
-
-        // Add source to destination's children
-        {
-            let Some( node ) = self.node_mut( destination ) else {
-                return Err( TreeError::RetrievingNode( destination ) )
-            };
-            let children = node.children.as_mut().unwrap();
-            let destination_position = match position {
-                Some( value ) => value,
-                None => children.len() - 1
+        if order.len() != edges.len() {
+            let ( local, _ ) = edges.iter().find( |( local, _ )| !order.iter().any( |( x, _ )| x == local ) ).unwrap();
+            return Err( TreeError::InvalidEdge( *local ) );
+        }
+        let mut indices = HashMap::<usize, usize>::new();
+        for ( local, local_parent ) in order {
+            let node_index = match local_parent {
+                None => parent,
+                Some( local_parent ) => indices[ &local_parent ],
             };
-            children.insert( destination_position, source );
+            indices.insert( local, self.insert( node_index, features, None, None )? );
         }
-
-        // Change source's parent to destination
-        let Some( node ) = self.node_mut( source ) else {
-            return Err( TreeError::RetrievingNode( source ) )
-        };
-        node.parent = Some( destination );
-        Ok( () )
+        Ok( indices[ &local_root ] )
     }
 
-    // -- information methods --
-
-    /// Check if `node_index` exists in the tree.
+    /// Create a node with the `data`, and insert it into the children of the specified node `parent` at the position
+    /// keeping the children sorted, as determined by the `compare` closure. Returns the index of the created node.
+    /// 
+    /// The `compare` closure is passed the `data` of the new node, the index of an existing child and the tree, and
+    /// returns the ordering of the new node relative to the child. The children are assumed to be already sorted, thus
+    /// the position is found using a binary search. The new node is placed after any children that are equal to it.
+    /// 
+    /// The `data` is handled the same as [`insert_with_data`].
     /// 
     /// # Examples
     /// 
     /// ```
     /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// use core::any::Any;
+    /// use core::cmp::Ordering;
     /// 
     /// let mut tree = Tree::new();
-    /// tree.insert( 53, ALLOW_CHILDREN | ALLOW_DATA, None, None ).ok();
-    /// assert!( tree.exists( 0 ) );
-    /// assert!( !tree.exists( 1 ) );
+    /// tree.insert( 425, ALLOW_CHILDREN, None, None ).ok();
+    /// let by_number = |data: &Vec<Box<dyn Any>>, child: usize, tree: &Tree| -> Ordering {
+    ///     let number = data[ 0 ].downcast_ref::<i32>().unwrap();
+    ///     number.cmp( tree.data_ref( child ).unwrap()[ 0 ].downcast_ref::<i32>().unwrap() )
+    /// };
+    /// tree.insert_sorted( 0, ALLOW_DATA, None, None, vec![ Box::new( 3i32 ) ], by_number ).ok();
+    /// tree.insert_sorted( 0, ALLOW_DATA, None, None, vec![ Box::new( 1i32 ) ], by_number ).ok();
+    /// tree.insert_sorted( 0, ALLOW_DATA, None, None, vec![ Box::new( 2i32 ) ], by_number ).ok();
+    /// assert_eq!( tree.children( 0 ).unwrap(), &vec![ 2, 3, 1 ] );
     /// ```
-    pub fn exists( &self, node_index: usize ) -> bool {
-        if let Some( option ) = self.nodes.get( node_index ) {
-            if let Some( _ ) = option {
-                return true;
-            }
-        }
-        false
+    /// 
+    /// [`insert_with_data`]: Tree::insert_with_data
+    pub fn insert_sorted<F>(
+        &mut self,
+        parent: usize,
+        features: u8,
+        node_type: Option<Box<dyn Any>>,
+        data_type: Option<Box<dyn Any>>,
+        data: Vec<Box<dyn Any>>,
+        compare: F,
+    ) -> Result<usize, TreeError>
+    where
+        F: Fn( &Vec<Box<dyn Any>>, usize, &Tree ) -> Ordering
+    {
+        let position = self.children( parent )?
+            .partition_point( |&child| compare( &data, child, self ) != Ordering::Less );
+        let index = self.insert_with_data( parent, features, node_type, data_type, data )?;
+        self.reparent( index, parent, Position::At( position ) )?;
+        Ok( index )
     }
 
-    /// Obtain reference to the node type for the specified node `node_index`.
+    /// Move all the nodes of each of the trees `others` into the tree, where the root node of each of the trees becomes
+    /// the next child of the specified node `parent`. Returns the indices of the grafted root nodes in order.
+    /// 
+    /// The nodes keep their features, node types, data types and data, though their indices will differ. Only the nodes
+    /// reachable from the root node of each tree are grafted, and empty trees are skipped. The `parent` node is
+    /// validated before any tree is grafted.
     /// 
     /// # Examples
     /// 
@@ -502,19 +642,33 @@ impl Tree {
     /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
     /// 
     /// let mut tree = Tree::new();
-    /// tree.insert( 514, ALLOW_CHILDREN | ALLOW_DATA, Some( Box::new( "node type 2".to_string() ) ), None ).ok();
-    /// let type_any_ref = tree.node_type( 0 ).ok().unwrap().as_ref().unwrap();
-    /// let type_usize = type_any_ref.downcast_ref::<String>().unwrap();
-    /// assert_eq!( *type_usize, "node type 2" );
+    /// tree.insert( 4, ALLOW_CHILDREN, None, None ).ok();
+    /// let mut other = Tree::new();
+    /// other.insert( 0, ALLOW_CHILDREN, None, None ).ok();
+    /// other.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// let roots = tree.graft_many( 0, vec![ other, Tree::new() ] ).unwrap();
+    /// assert_eq!( roots, vec![ 1 ] );
+    /// assert_eq!( tree.count(), 3, "3 nodes are present." );
+    /// assert_eq!( tree.children( 1 ).unwrap(), &vec![ 2 ] );
     /// ```
-    pub fn node_type( &self, node_index: usize ) -> Result<&Option<Box<dyn Any>>, TreeError> {
-        let Some( index_node ) = self.node( node_index ) else {
-            return Err( TreeError::RetrievingNode( node_index ) )
-        };
-        Ok( &index_node.node_type )
+    pub fn graft_many( &mut self, parent: usize, others: Vec<Tree> ) -> Result<Vec<usize>, TreeError> {
+        self.children( parent )?;
+        self.reserve_for( others.iter().map( |other| other.count() ).sum() );
+        let mut roots = Vec::<usize>::new();
+        for mut other in others {
+            let Some( other_root ) = other.root else {
+                continue;
+            };
+            roots.push( other.move_subtree_into( other_root, self, parent )? );
+        }
+        Ok( roots )
     }
 
-    /// Obtain reference to the node's features for the specified node `node_index`.
+    /// Reserve space for creating at least `additional_nodes` more nodes without the internal vector of nodes being
+    /// reallocated. The empty nodes (deleted/taken) are counted towards the `additional_nodes`, as they are reused when
+    /// creating nodes.
+    /// 
+    /// The [`graft_many`] method already reserves space for all the nodes of the grafted trees.
     /// 
     /// # Examples
     /// 
@@ -522,20 +676,26 @@ impl Tree {
     /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
     /// 
     /// let mut tree = Tree::new();
-    /// tree.insert( 16, ALLOW_CHILDREN | ALLOW_DATA, None, None ).ok();
-    /// let features_ref = tree.features( 0 );
-    /// let features = *features_ref.as_ref().unwrap();
-    /// assert_eq!( features & ALLOW_CHILDREN, ALLOW_CHILDREN );
-    /// assert_eq!( features & ALLOW_DATA, ALLOW_DATA );
+    /// tree.insert( 4, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.reserve_for( 100 );
+    /// let capacity = tree.capacity();
+    /// assert!( capacity >= 101 );
+    /// for _ in 0..100 {
+    ///     tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// }
+    /// assert_eq!( tree.capacity(), capacity, "No reallocation." );
     /// ```
-    pub fn features( &self, node_index: usize ) -> Result<&u8, TreeError> {
-        let Some( index_node ) = self.node( node_index ) else {
-            return Err( TreeError::RetrievingNode( node_index ) )
-        };
-        Ok( &index_node.features )
+    /// 
+    /// [`graft_many`]: Tree::graft_many
+    pub fn reserve_for( &mut self, additional_nodes: usize ) {
+        self.nodes.reserve( additional_nodes.saturating_sub( self.hole_count() ) );
     }
 
-    /// Obtain reference to the node's immediate parent for the specified node `node_index`.
+    /// Remove the subtree `node_index` from the tree, and return it as a new tree where the node is the root node.
+    /// 
+    /// The nodes keep their features, node types, data types and data, though their indices in the new tree will
+    /// differ, as the indices are reassigned from 0 in pre-order. The removed nodes become empty nodes in the tree,
+    /// except when splitting off the root node, which leaves the tree empty.
     /// 
     /// # Examples
     /// 
@@ -543,56 +703,78 @@ impl Tree {
     /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
     /// 
     /// let mut tree = Tree::new();
-    /// tree.insert( 23, ALLOW_CHILDREN | ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 4, ALLOW_CHILDREN, None, None ).ok();
     /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
-    /// assert_eq!( tree.parent( 1 ).ok(), Some( 0 ), "Parent is root node." );
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 2, ALLOW_DATA, None, None ).ok();
+    /// let other = tree.split_off( 2 ).unwrap();
+    /// assert_eq!( tree.count(), 2, "2 nodes are present." );
+    /// assert_eq!( other.count(), 2, "2 nodes are split off." );
+    /// assert_eq!( other.children( 0 ).unwrap(), &vec![ 1 ] );
     /// ```
-    pub fn parent( &self, node_index: usize ) -> Result<usize, TreeError> {
-        if Some( node_index ) == self.root {
-            return Err( TreeError::RootHasNoParent( node_index ) );
+    pub fn split_off( &mut self, node_index: usize ) -> Result<Tree, TreeError> {
+        let position = self.child_position( node_index )?;
+        let parent = self.node( node_index ).unwrap().parent;
+        let indices = self.subtree( node_index )?;
+        let mut tree = Tree::new();
+        self.move_subtree_into( node_index, &mut tree, 0 )?;
+        match ( parent, position ) {
+            ( Some( parent ), Some( position ) ) => {
+                self.node_mut( parent ).unwrap().children.as_mut().unwrap().remove( position );
+            },
+            _ => self.clear()
         }
-        let Some( index_node ) = self.node( node_index ) else {
-            return Err( TreeError::RetrievingNode( node_index ) )
-        };
-        Ok( *index_node.parent.as_ref().unwrap() )
+        for index in indices {
+            self.notify( Mutation::Delete( index ) );
+        }
+        Ok( tree )
     }
 
-    /// Determine if a node `is_ancestor` is an ancestor of the specified node `node_index`. This method will iterate
-    /// through the parents until the root node.
+    /// Replace the child at `position` of the specified node `parent` with the nodes of the `replacement` tree, and
+    /// return the replaced child's subtree as a new tree (see [`split_off`]).
     /// 
-    /// `true` is returned if the node is found to be ancestor of the specified node, else `false` is returned.
+    /// The root node of the `replacement` tree takes the position of the replaced child (see [`graft_many`]). An empty
+    /// `replacement` tree just removes the child.
     /// 
-    /// # Example
+    /// # Examples
     /// 
     /// ```
     /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
     /// 
     /// let mut tree = Tree::new();
-    /// tree.insert( 338, ALLOW_CHILDREN, None, None, ).ok();
-    /// tree.insert( 0, ALLOW_CHILDREN, None, None, ).ok();
-    /// tree.insert( 0, ALLOW_CHILDREN, None, None, ).ok();
-    /// let last = tree.insert( 1, ALLOW_CHILDREN, None, None, ).ok().unwrap();
-    /// assert_eq!( last, 3 );
-    /// let mut result = tree.is_ancestor_of( 3, 0 ).unwrap();
-    /// assert!( result, "Root is grandparent of node 3." );
-    /// result = tree.is_ancestor_of( 3, 2 ).unwrap();
-    /// assert!( !result, "Node 2 is not a parent of node 3." );
+    /// tree.insert( 4, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// let mut replacement = Tree::new();
+    /// replacement.insert( 0, ALLOW_CHILDREN, None, None ).ok();
+    /// let replaced = tree.replace_child( 0, 0, replacement ).unwrap();
+    /// assert_eq!( replaced.count(), 1 );
+    /// assert_eq!( tree.children( 0 ).unwrap(), &vec![ 1, 2 ] );
+    /// assert!( tree.children( 1 ).is_ok(), "Node 1 is the replacement." );
     /// ```
-    pub fn is_ancestor_of( &self, node_index: usize, is_ancestor: usize ) -> Result<bool, TreeError> {
-        let parent = match self.parent( node_index ) {
-            Ok( result ) => result,
-            Err( error ) => return match error {
-                TreeError::RootHasNoParent( _ ) => Ok( false ),
-                _ => Err( error )
-            }
+    /// 
+    /// [`split_off`]: Tree::split_off
+    /// [`graft_many`]: Tree::graft_many
+    pub fn replace_child( &mut self, parent: usize, position: usize, replacement: Tree ) -> Result<Tree, TreeError> {
+        let Some( &child ) = self.children( parent )?.get( position ) else {
+            return Err( TreeError::ExceedsChildren( position, parent ) )
         };
-        if parent == is_ancestor {
-            return Ok( true );
+        let replaced = self.split_off( child )?;
+        if let Some( &root ) = self.graft_many( parent, vec![ replacement ] )?.first() {
+            self.reparent( root, parent, Position::At( position ) )?;
         }
-        Ok( self.is_ancestor_of( parent, is_ancestor )? )
+        Ok( replaced )
     }
 
-    /// Obtain reference to the node children for the specified node `node_index`.
+    /// Replace the features, node type and data type of the specified node `node_index`, while keeping its index,
+    /// parent and children.
+    /// 
+    /// The [`ALLOW_CHILDREN`] feature can't be removed while the node still has children. When the [`ALLOW_DATA`]
+    /// feature is added, the node starts with no data, and when removed, the node's data is destroyed.
+    /// 
+    /// # WARNING
+    /// 
+    /// The data of the node will be destroyed if the [`ALLOW_DATA`] feature is removed.
     /// 
     /// # Examples
     /// 
@@ -600,22 +782,53 @@ impl Tree {
     /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
     /// 
     /// let mut tree = Tree::new();
-    /// tree.insert( 624, ALLOW_CHILDREN | ALLOW_DATA, None, None ).ok();
-    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
-    /// let children = tree.children( 0 ).ok().unwrap();
-    /// assert_eq!( children.len(), 1, "Has 1 child." );
+    /// tree.insert( 4, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.replace_node_in_place( 1, ALLOW_DATA, Some( Box::new( "Leaf".to_string() ) ), None ).ok();
+    /// assert_eq!( *tree.features( 1 ).unwrap(), ALLOW_DATA );
+    /// assert!( tree.data_ref( 1 ).unwrap().is_empty(), "Node 1 has no data." );
+    /// assert!( tree.replace_node_in_place( 0, ALLOW_DATA, None, None ).is_err(), "Root still has children." );
     /// ```
-    pub fn children( &self, node_index: usize ) -> Result<&Vec<usize>, TreeError> {
-        let Some( index_node ) = self.node( node_index ) else {
+    /// 
+    /// [`ALLOW_CHILDREN`]: ALLOW_CHILDREN
+    /// [`ALLOW_DATA`]: ALLOW_DATA
+    pub fn replace_node_in_place(
+        &mut self,
+        node_index: usize,
+        features: u8,
+        node_type: Option<Box<dyn Any>>,
+        data_type: Option<Box<dyn Any>>,
+    ) -> Result<(), TreeError> {
+        let Some( index_node ) = self.node_mut( node_index ) else {
             return Err( TreeError::RetrievingNode( node_index ) )
         };
-        if index_node.features & ALLOW_CHILDREN != ALLOW_CHILDREN {
-            return Err( TreeError::NoChildrenAllowed( node_index ) );
+        if features & ALLOW_CHILDREN != ALLOW_CHILDREN {
+            if let Some( children ) = index_node.children.as_ref() {
+                if !children.is_empty() {
+                    return Err( TreeError::HasChildren( node_index ) );
+                }
+            }
+            index_node.children = None;
+        } else if index_node.children.is_none() {
+            index_node.children = Some( Vec::<usize>::new() );
         }
-        Ok( &index_node.children.as_ref().unwrap() )
+        if features & ALLOW_DATA != ALLOW_DATA {
+            index_node.data = None;
+        } else if index_node.data.is_none() {
+            index_node.data = Some( Vec::<Box<dyn Any>>::new() );
+        }
+        index_node.features = features;
+        index_node.node_type = node_type;
+        index_node.data_type = data_type;
+        self.notify( Mutation::Update( node_index ) );
+        Ok( () )
     }
 
-    /// Convenience method to obtain the first child of the node `node_index`.
+    /// Change the features of the specified node `node_index`.
+    /// 
+    /// The [`ALLOW_CHILDREN`] feature can't be removed while the node still has children, and the [`ALLOW_DATA`]
+    /// feature can't be removed while the node still has data. When the [`ALLOW_DATA`] feature is added, the node
+    /// starts with no data.
     /// 
     /// # Examples
     /// 
@@ -623,20 +836,50 @@ impl Tree {
     /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
     /// 
     /// let mut tree = Tree::new();
-    /// tree.insert( 624, ALLOW_CHILDREN | ALLOW_DATA, None, None ).ok();
-    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
-    /// let first = tree.first( 0 ).ok().unwrap();
-    /// assert_eq!( first, 1, "First child is index 1." );
+    /// tree.insert( 4, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.set_features( 1, ALLOW_CHILDREN | ALLOW_DATA ).ok();
+    /// tree.data_mut( 1 ).unwrap().push( Box::new( "String data".to_string() ) );
+    /// assert!( tree.set_features( 1, ALLOW_CHILDREN ).is_err(), "Node 1 still has data." );
+    /// assert!( tree.set_features( 0, ALLOW_DATA ).is_err(), "Root still has children." );
     /// ```
-    pub fn first( &self, node_index: usize ) -> Result<usize, TreeError> {
-        let children = self.children( node_index )?;
-        let Some( index ) = children.first() else {
-            return Err( TreeError::NoChildrenFound( node_index ) )
+    /// 
+    /// [`ALLOW_CHILDREN`]: ALLOW_CHILDREN
+    /// [`ALLOW_DATA`]: ALLOW_DATA
+    pub fn set_features( &mut self, node_index: usize, features: u8 ) -> Result<(), TreeError> {
+        let Some( index_node ) = self.node_mut( node_index ) else {
+            return Err( TreeError::RetrievingNode( node_index ) )
         };
-        Ok( *index )
+        let allow_children = features & ALLOW_CHILDREN == ALLOW_CHILDREN;
+        let allow_data = features & ALLOW_DATA == ALLOW_DATA;
+        if !allow_children && index_node.children.as_ref().map_or( false, |children| !children.is_empty() ) {
+            return Err( TreeError::HasChildren( node_index ) );
+        }
+        if !allow_data && index_node.data.as_ref().map_or( false, |data| !data.is_empty() ) {
+            return Err( TreeError::HasData( node_index ) );
+        }
+        if !allow_children {
+            index_node.children = None;
+        } else if index_node.children.is_none() {
+            index_node.children = Some( Vec::<usize>::new() );
+        }
+        if !allow_data {
+            index_node.data = None;
+        } else if index_node.data.is_none() {
+            index_node.data = Some( Vec::<Box<dyn Any>>::new() );
+        }
+        index_node.features = features;
+        self.notify( Mutation::Update( node_index ) );
+        Ok( () )
     }
 
-    /// Convenience method to obtain the last child of the node `node_index`.
+    /// Deletes the specified node `node_index` from the tree.
+    /// 
+    /// # WARNING
+    /// 
+    /// This is a destructive method that destroys the data when deleting the node.
+    /// 
+    /// If wanting the data, use [`take`] method instead.
     /// 
     /// # Examples
     /// 
@@ -644,20 +887,60 @@ impl Tree {
     /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
     /// 
     /// let mut tree = Tree::new();
-    /// tree.insert( 624, ALLOW_CHILDREN | ALLOW_DATA, None, None ).ok();
-    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
-    /// let last = tree.last( 0 ).ok().unwrap();
-    /// assert_eq!( last, 1, "Last child is index 1." );
+    /// tree.insert( 68, ALLOW_CHILDREN, None, None ).ok();
+    /// assert_eq!( tree.count(), 1, "1 node is present." );
+    /// match tree.delete( 0 ) {
+    ///     Err( error ) => println!( "{}", error ),
+    ///     Ok( _ ) => println!( "Succeeded to delete node." )
+    /// }
+    /// assert_eq!( tree.count(), 0, "0 nodes are present." );
     /// ```
-    pub fn last( &self, node_index: usize ) -> Result<usize, TreeError> {
-        let children = self.children( node_index )?;
-        let Some( index ) = children.last() else {
-            return Err( TreeError::NoChildrenFound( node_index ) )
-        };
-        Ok( *index )
+    /// 
+    /// [`take`]: Tree::take
+    pub fn delete( &mut self, node_index: usize ) -> Result<(), TreeError> {
+        let mut _parent = None;
+        {
+            let Some( index_node ) = self.node( node_index ) else {
+                return Err( TreeError::RetrievingNode( node_index ) )
+            };
+            if
+                index_node.features & ALLOW_CHILDREN == ALLOW_CHILDREN
+                && !index_node.children.as_ref().unwrap().is_empty()
+            {
+                return Err( TreeError::HasChildren( node_index ) );
+            }
+            _parent = index_node.parent;
+        }
+        {
+            if let Some( parent ) = _parent {
+                let Some( parent_node ) = self.node_mut( parent ) else {
+                    return Err( TreeError::RetrievingNode( parent ) )
+                };
+                let children = parent_node.children.as_mut().unwrap();
+                let Some( _position ) = children.iter().position( |&x| x == node_index ) else {
+                    return Err( TreeError::MissingInParent( node_index, parent ) ); // Serious integrity issue.
+                };
+                children.remove( _position );
+            }
+        }
+        let mut _node_ref = self.nodes.get_mut( node_index ).unwrap();
+        *_node_ref = None;
+        if Some( node_index ) == self.root {
+            self.root = None;
+            self.nodes.clear();
+        }
+        self.notify( Mutation::Delete( node_index ) );
+        Ok( () )
     }
 
-    /// Convenience method to obtain the nth child `position` of the node `node_index`.
+    /// Deletes all the specified nodes `indices` from the tree, and return the number of nodes deleted.
+    /// 
+    /// All the nodes must exist and have no children. The nodes are validated before any node is deleted, thus either
+    /// all the nodes are deleted or none are deleted. Repeated indices are only deleted once.
+    /// 
+    /// # WARNING
+    /// 
+    /// This is a destructive method that destroys the data when deleting the nodes.
     /// 
     /// # Examples
     /// 
@@ -665,20 +948,33 @@ impl Tree {
     /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
     /// 
     /// let mut tree = Tree::new();
-    /// tree.insert( 624, ALLOW_CHILDREN | ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 68, ALLOW_CHILDREN, None, None ).ok();
     /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
-    /// let child = tree.child( 0, 0 ).ok().unwrap();
-    /// assert_eq!( child, 1, "Has 1 child with index 1." );
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// assert!( tree.bulk_delete( &[ 0, 1 ] ).is_err(), "Root node still has children." );
+    /// assert_eq!( tree.bulk_delete( &[ 1, 2 ] ).unwrap(), 2, "Deleted 2 nodes." );
+    /// assert_eq!( tree.count(), 1, "1 node is present." );
     /// ```
-    pub fn child( &self, node_index: usize, position: usize ) -> Result<usize, TreeError> {
-        let children = self.children( node_index )?;
-        let Some( index ) = children.get( position ) else {
-            return Err( TreeError::NoChildrenFound( node_index ) )
-        };
-        Ok( *index )
+    pub fn bulk_delete( &mut self, indices: &[usize] ) -> Result<usize, TreeError> {
+        let mut unique = Vec::<usize>::new();
+        for index in indices {
+            let Some( index_node ) = self.node( *index ) else {
+                return Err( TreeError::RetrievingNode( *index ) )
+            };
+            if index_node.children.as_ref().map_or( false, |children| !children.is_empty() ) {
+                return Err( TreeError::HasChildren( *index ) );
+            }
+            if !unique.contains( index ) {
+                unique.push( *index );
+            }
+        }
+        for index in unique.iter() {
+            self.delete( *index )?;
+        }
+        Ok( unique.len() )
     }
 
-    /// Obtain the depth of the specified node `node_index` from the root.
+    /// Deletes the specified node `node_index` from the tree, and return its data (if any).
     /// 
     /// # Examples
     /// 
@@ -686,32 +982,55 @@ impl Tree {
     /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
     /// 
     /// let mut tree = Tree::new();
-    /// tree.insert( 72, ALLOW_CHILDREN | ALLOW_DATA, None, None ).ok();
-    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
-    /// let depth = tree.depth( 1 ).ok().unwrap();
-    /// assert_eq!( depth, 1, "Has 1 child." );
+    /// tree.insert( 128, ALLOW_CHILDREN | ALLOW_DATA, None, None ).ok();
+    /// tree.data_mut( 0 ).unwrap().push( Box::new( "String data".to_string() ) );
+    /// assert_eq!( tree.count(), 1, "1 node is present." );
+    /// let mut data_vec = tree.take( 0 ).ok().unwrap().unwrap(); // Deleting root node, and take data.
+    /// let data = data_vec.pop().unwrap().downcast::<String>().ok().unwrap();
+    /// assert_eq!( tree.count(), 0, "0 nodes are present." );
+    /// assert_eq!( *data, "String data".to_string(), "Data of node is a string" );
     /// ```
-    pub fn depth( &self, mut node_index: usize ) -> Result<usize, TreeError> {
-        let mut depth = 0;
-        loop {
-            if let Some( node ) = self.node( node_index ) {
-                if let Some( parent ) = node.parent.as_ref() {
-                    node_index = *parent;
-                    depth += 1;
-                }
-                else {
-                    return Ok( depth );
-                }
+    pub fn take( &mut self, node_index: usize ) -> Result<Option<Vec<Box<dyn Any>>>, TreeError> {
+        let mut _parent = None;
+        {
+            let Some( index_node ) = self.node( node_index ) else {
+                return Err( TreeError::RetrievingNode( node_index ) )
+            };
+            if
+                index_node.features & ALLOW_CHILDREN == ALLOW_CHILDREN
+                && !index_node.children.as_ref().unwrap().is_empty()
+            {
+                return Err( TreeError::HasChildren( node_index ) );
             }
-            else {
-                return Err( TreeError::RetrievingNode( node_index ) );
+            _parent = index_node.parent;
+        }
+        {
+            if let Some( parent ) = _parent {
+                let Some( parent_node ) = self.node_mut( parent ) else {
+                    return Err( TreeError::RetrievingNode( parent ) )
+                };
+                let children = parent_node.children.as_mut().unwrap();
+                let Some( _position ) = children.iter().position( |&x| x == node_index ) else {
+                    return Err( TreeError::MissingInParent( node_index, parent ) ); // Serious integrity issue.
+                };
+                children.remove( _position );
             }
         }
+        let mut _node_ref = self.nodes.get_mut( node_index ).unwrap();
+        let node = _node_ref.take().unwrap();
+        *_node_ref = None;
+        if Some( node_index ) == self.root {
+            self.root = None;
+            self.nodes.clear();
+        }
+        self.notify( Mutation::Delete( node_index ) );
+        Ok( node.data )
     }
 
-    /// Get length of internal vector of nodes, including the empty nodes (deleted/taken).
+    /// Deletes the specified node `node_index` from the tree, by replacing the node with its children in its parent's
+    /// children, and return its data (if any).
     /// 
-    /// For actual number of nodes in the tree, use [`count`] method.
+    /// The root node can't be collapsed, as its children would have no parent.
     /// 
     /// # Examples
     /// 
@@ -719,22 +1038,3169 @@ impl Tree {
     /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
     /// 
     /// let mut tree = Tree::new();
-    /// tree.insert( 297, ALLOW_CHILDREN | ALLOW_DATA, None, None ).ok();
-    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
-    /// match tree.delete( 1 ) {
-    ///     Err( error ) => println!( "{}", error ),
-    ///     Ok( _ ) => println!( "Succeeded to delete node." )
-    /// }
+    /// tree.insert( 128, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 1, ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 1, ALLOW_DATA, None, None ).ok();
+    /// tree.collapse( 1 ).ok();
+    /// assert_eq!( tree.children( 0 ).unwrap(), &vec![ 2, 3 ], "Node 1 is replaced with its children." );
+    /// assert_eq!( tree.count(), 3, "3 nodes are present." );
+    /// ```
+    pub fn collapse( &mut self, node_index: usize ) -> Result<Option<Vec<Box<dyn Any>>>, TreeError> {
+        let Some( index_node ) = self.node( node_index ) else {
+            return Err( TreeError::RetrievingNode( node_index ) )
+        };
+        let Some( parent ) = index_node.parent else {
+            return Err( TreeError::RootHasNoParent( node_index ) )
+        };
+        let Some( position ) = self.children( parent )?.iter().position( |&x| x == node_index ) else {
+            return Err( TreeError::MissingInParent( node_index, parent ) ); // Serious integrity issue.
+        };
+        let node = self.nodes.get_mut( node_index ).unwrap().take().unwrap();
+        let children = node.children.unwrap_or_default();
+        for child in children.iter() {
+            if let Some( child_node ) = self.node_mut( *child ) {
+                child_node.parent = Some( parent );
+            }
+        }
+        self.node_mut( parent ).unwrap().children.as_mut().unwrap().splice( position..=position, children.clone() );
+        self.notify( Mutation::Delete( node_index ) );
+        for child in children {
+            self.notify( Mutation::Move( child ) );
+        }
+        Ok( node.data )
+    }
+
+    /// Move all the children of the specified node `node_index` up one level to become children of the node's parent,
+    /// and return the number of children moved.
+    /// 
+    /// The children are placed in order at the node's position in its parent's children, followed by the node, which
+    /// is kept without children. The root node can't be flattened, as its children would have no parent.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 128, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 1, ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 1, ALLOW_DATA, None, None ).ok();
+    /// assert_eq!( tree.flatten_children( 1 ).unwrap(), 2 );
+    /// assert_eq!( tree.children( 0 ).unwrap(), &vec![ 2, 3, 1 ], "Children of node 1 are moved up." );
+    /// assert!( tree.children( 1 ).unwrap().is_empty(), "Node 1 has no children." );
+    /// ```
+    pub fn flatten_children( &mut self, node_index: usize ) -> Result<usize, TreeError> {
+        let children = self.children( node_index )?.clone();
+        let Some( position ) = self.child_position( node_index )? else {
+            return Err( TreeError::RootHasNoParent( node_index ) )
+        };
+        let parent = self.node( node_index ).unwrap().parent.unwrap();
+        for child in children.iter() {
+            if let Some( child_node ) = self.node_mut( *child ) {
+                child_node.parent = Some( parent );
+            }
+        }
+        self.node_mut( node_index ).unwrap().children.as_mut().unwrap().clear();
+        let count = children.len();
+        self.node_mut( parent ).unwrap().children.as_mut().unwrap().splice( position..position, children.clone() );
+        for child in children {
+            self.notify( Mutation::Move( child ) );
+        }
+        Ok( count )
+    }
+
+    /// Delete all the nodes for which the `predicate` returns `false`, and return the number of nodes deleted.
+    /// 
+    /// Only leaf nodes (nodes without children) are deleted, as deleting a node with children would orphan its
+    /// children. The nodes are visited from the bottom of the tree upwards, thus a node whose children have all been
+    /// deleted becomes a leaf node and is then also checked (cascading). A node failing the `predicate` that still has
+    /// surviving children is kept.
+    /// 
+    /// # WARNING
+    /// 
+    /// All data in the deleted nodes will be destroyed.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 128, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 1, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// let removed = tree.retain( |index, tree| index == 0 || tree.data_ref( index ).is_ok() ).unwrap();
+    /// assert_eq!( removed, 2, "Nodes 1 and 2 are deleted." );
+    /// assert_eq!( tree.children( 0 ).unwrap(), &vec![ 3 ] );
+    /// ```
+    pub fn retain<F>( &mut self, predicate: F ) -> Result<usize, TreeError>
+    where
+        F: Fn( usize, &Tree ) -> bool
+    {
+        let Some( root ) = self.root else {
+            return Ok( 0 );
+        };
+        let mut removed = 0;
+
+        // Visiting the nodes in reverse pre-order ensures the children are visited before their parent.
+        for index in self.subtree( root )?.into_iter().rev() {
+            let is_leaf = self.node( index ).unwrap().children.as_ref().map_or( true, |children| children.is_empty() );
+            if is_leaf && !predicate( index, self ) {
+                self.delete( index )?;
+                removed += 1;
+            }
+        }
+        Ok( removed )
+    }
+
+    /// Clear the tree of all nodes.
+    /// 
+    /// # WARNING
+    /// 
+    /// All data in the nodes will be destroyed.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 254, ALLOW_CHILDREN, None, None ).ok();
+    /// assert_eq!( tree.count(), 1, "1 node is present." );
+    /// tree.clear();
+    /// assert_eq!( tree.count(), 0, "0 nodes are present." );
+    /// ```
+    pub fn clear( &mut self ) {
+        let indices = self.nodes
+            .iter()
+            .enumerate()
+            .filter_map( |( index, node )| node.as_ref().map( |_| index ) )
+            .collect::<Vec<usize>>();
+        self.root = None;
+        self.nodes.clear();
+        for index in indices {
+            self.notify( Mutation::Delete( index ) );
+        }
+    }
+
+    /// Remove all the nodes that can't be reached from the root node, and return the number of nodes removed.
+    /// 
+    /// All the nodes reachable from the root node are marked, then every other node is removed (mark and sweep). If
+    /// there is no root node, then all the nodes are removed.
+    /// 
+    /// # WARNING
+    /// 
+    /// All data in the removed nodes will be destroyed.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 254, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// assert_eq!( tree.remove_unreachable(), 0, "All nodes are reachable." );
+    /// assert_eq!( tree.count(), 2, "2 nodes are present." );
+    /// ```
+    pub fn remove_unreachable( &mut self ) -> usize {
+        let mut reachable = vec![ false; self.nodes.len() ];
+        if let Some( root ) = self.root {
+            if let Ok( indices ) = self.subtree( root ) {
+                for index in indices {
+                    reachable[ index ] = true;
+                }
+            }
+        }
+        let mut removed = Vec::<usize>::new();
+        for ( index, node ) in self.nodes.iter_mut().enumerate() {
+            if node.is_some() && !reachable[ index ] {
+                *node = None;
+                removed.push( index );
+            }
+        }
+        if self.root.is_none() {
+            self.nodes.clear();
+        }
+        for &index in removed.iter() {
+            self.notify( Mutation::Delete( index ) );
+        }
+        removed.len()
+    }
+
+    /// Remove the stale indices from the children of all the nodes, and return the number of stale indices removed.
+    /// 
+    /// A child index is stale when the child node does not exist, or when the child node's parent is not the node.
+    /// Unlike [`validate_node`], which only reports the first problem of a node, this method repairs the children.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 254, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// assert_eq!( tree.repair_children(), 0, "All children are consistent." );
+    /// ```
+    /// 
+    /// [`validate_node`]: Tree::validate_node
+    pub fn repair_children( &mut self ) -> usize {
+        let mut removed = 0;
+        for index in 0..self.nodes.len() {
+            let Some( children ) = self.node( index ).and_then( |node| node.children.as_ref() ) else {
+                continue;
+            };
+            let retained = children
+                .iter()
+                .copied()
+                .filter( |&child| self.node( child ).map_or( false, |node| node.parent == Some( index ) ) )
+                .collect::<Vec<usize>>();
+            let stale = children.len() - retained.len();
+            self.node_mut( index ).unwrap().children = Some( retained );
+            if stale > 0 {
+                removed += stale;
+                self.notify( Mutation::Children( index ) );
+            }
+        }
+        removed
+    }
+
+    /// Recover the root node when it has been lost, by setting the root node to the only node without a parent, and
+    /// return the index of the root node.
+    /// 
+    /// If the tree already has a root node, then the root node is returned unchanged. Otherwise there must be exactly
+    /// one node without a parent. The error [`TreeError::AmbiguousRoot`] is returned containing the number of nodes
+    /// without a parent when there are several, and the error [`TreeError::NoRootNode`] is returned when there are
+    /// none (such as for an empty tree).
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// assert!( tree.rebuild_root().is_err(), "Empty tree has no root node." );
+    /// tree.insert( 254, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// assert_eq!( tree.rebuild_root().unwrap(), 0 );
+    /// ```
+    /// 
+    /// [`TreeError::AmbiguousRoot`]: TreeError::AmbiguousRoot
+    /// [`TreeError::NoRootNode`]: TreeError::NoRootNode
+    pub fn rebuild_root( &mut self ) -> Result<usize, TreeError> {
+        if let Some( root ) = self.root {
+            return Ok( root );
+        }
+        let orphans = self.parentless().collect::<Vec<usize>>();
+        if orphans.is_empty() {
+            return Err( TreeError::NoRootNode );
+        }
+        if orphans.len() != 1 {
+            return Err( TreeError::AmbiguousRoot( orphans.len() ) );
+        }
+        self.root = Some( orphans[ 0 ] );
+        Ok( orphans[ 0 ] )
+    }
+
+    /// Delete all the nodes deeper than `max_depth`, and return the number of nodes deleted. The root node is at depth
+    /// 0, and thus is never deleted.
+    /// 
+    /// An error is returned when a node to be deleted is missing in the children of its parent, which indicates the
+    /// tree's integrity is compromised.
+    /// 
+    /// # WARNING
+    /// 
+    /// All data in the deleted nodes will be destroyed.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 338, ALLOW_CHILDREN, None, None, ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None, ).ok();
+    /// tree.insert( 1, ALLOW_DATA, None, None, ).ok();
+    /// tree.insert( 1, ALLOW_DATA, None, None, ).ok();
+    /// assert_eq!( tree.trim_to_depth( 1 ).unwrap(), 2, "Deleted 2 nodes." );
+    /// assert_eq!( tree.count(), 2, "2 nodes are present." );
+    /// ```
+    pub fn trim_to_depth( &mut self, max_depth: usize ) -> Result<usize, TreeError> {
+        let trim = self.flatten()
+            .iter()
+            .filter( |summary| summary.depth == max_depth + 1 )
+            .map( |summary| summary.index )
+            .collect::<Vec<usize>>();
+        let mut removed = 0;
+        for index in trim {
+            removed += self.delete_subtree( index )?;
+        }
+        Ok( removed )
+    }
+
+    /// Delete all the subtrees that contain no data, and return the number of nodes deleted. The root node is never
+    /// deleted.
+    /// 
+    /// A subtree contains no data when none of its nodes have any data items, where nodes that don't allow data count
+    /// as having no data. A subtree having at least one data item in any of its nodes is kept entirely.
+    /// 
+    /// An error is returned when a node to be deleted is missing in the children of its parent, which indicates the
+    /// tree's integrity is compromised.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 338, ALLOW_CHILDREN, None, None, ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None, ).ok();
+    /// tree.insert( 1, ALLOW_DATA, None, None, ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None, ).ok();
+    /// tree.insert( 3, ALLOW_DATA, None, None, ).ok();
+    /// tree.data_mut( 4 ).unwrap().push( Box::new( "String data".to_string() ) );
+    /// assert_eq!( tree.trim_empty_branches().unwrap(), 2, "Deleted nodes 1 and 2." );
+    /// assert_eq!( tree.children( 0 ).unwrap(), &vec![ 3 ] );
+    /// ```
+    pub fn trim_empty_branches( &mut self ) -> Result<usize, TreeError> {
+        let Some( root ) = self.root else {
+            return Ok( 0 );
+        };
+        let subtree = self.subtree( root )?;
+        let mut has_data = vec![ false; self.nodes.len() ];
+
+        // Visiting the nodes in reverse pre-order ensures the children are visited before their parent.
+        for &index in subtree.iter().rev() {
+            let node = self.node( index ).unwrap();
+            has_data[ index ] = node.data.as_ref().map_or( false, |data| !data.is_empty() )
+                || node.children.as_ref().map_or( false, |children| children.iter().any( |&child| has_data[ child ] ) );
+        }
+
+        // Only the top node of each empty subtree is deleted, which deletes the entire subtree.
+        let trim = subtree
+            .into_iter()
+            .filter( |&index| {
+                let parent = self.node( index ).unwrap().parent;
+                !has_data[ index ] && parent.map_or( false, |parent| parent == root || has_data[ parent ] )
+            } )
+            .collect::<Vec<usize>>();
+        let mut removed = 0;
+        for index in trim {
+            removed += self.delete_subtree( index )?;
+        }
+        Ok( removed )
+    }
+
+    /// Move part of the tree from one position to another within the tree.
+    /// 
+    /// The `destination` node must be able to have children, else move will not occur. Also the `source` node can't
+    /// already be an ancestor of `destination` node.
+    /// 
+    /// Parameter `position` is optional, and when passed as `None` the position is taken to be the last child of the
+    /// `destination` node. When a position is passed, it is the position of the `source` node in the `destination`
+    /// node's children after the move.
+    /// 
+    /// See [`reparent`] for details, as `move_nodes` is the same as `reparent` using [`Position::Back`] for `None`,
+    /// and [`Position::At`] for a position.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{ Tree, ALLOW_CHILDREN, ALLOW_DATA, TreeError };
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 338, ALLOW_CHILDREN, None, None, ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None, ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None, ).ok();
+    /// tree.insert( 1, ALLOW_CHILDREN, None, None, ).ok().unwrap();
+    /// tree.insert( 3, ALLOW_CHILDREN, None, None, ).ok();
+    /// assert_eq!( tree.parent( 3 ).unwrap(), 1, "Parent of node 3 must be 1." );
+    /// tree.move_nodes( 3, 2, None ).ok();
+    /// assert_eq!( tree.parent( 3 ).unwrap(), 2, "Parent of node 3 must be 2." );
+    /// assert_eq!( tree.parent( 4 ).unwrap(), 3, "Parent of node 4 must be 3." );
+    /// ```
+    /// 
+    /// [`reparent`]: Tree::reparent
+    /// [`Position::Back`]: Position::Back
+    /// [`Position::At`]: Position::At
+    pub fn move_nodes(
+        &mut self,
+        source: usize,
+        destination: usize,
+        position: Option<usize>
+    ) -> Result<(), TreeError> {
+        let at = match position {
+            Some( value ) => Position::At( value ),
+            None => Position::Back
+        };
+        self.reparent( source, destination, at )
+    }
+
+    /// Move part of the tree from one position to another within the tree, where the `position` is required.
+    /// 
+    /// The `position` is the position of the `source` node in the `destination` node's children after the move, and
+    /// the error [`TreeError::ExceedsChildren`] is returned when the `position` exceeds the number of children. The
+    /// `position` is never clamped. See [`reparent`] for details, as `move_nodes_strict` is the same as `reparent` using
+    /// [`Position::At`].
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{ Tree, ALLOW_CHILDREN, ALLOW_DATA, TreeError };
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 338, ALLOW_CHILDREN, None, None, ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None, ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None, ).ok();
+    /// tree.insert( 1, ALLOW_DATA, None, None, ).ok();
+    /// assert!( tree.move_nodes_strict( 3, 2, 1 ).is_err(), "Node 2 has no children." );
+    /// tree.move_nodes_strict( 3, 2, 0 ).ok();
+    /// assert_eq!( tree.parent( 3 ).unwrap(), 2, "Parent of node 3 must be 2." );
+    /// ```
+    /// 
+    /// [`TreeError::ExceedsChildren`]: TreeError::ExceedsChildren
+    /// [`reparent`]: Tree::reparent
+    /// [`Position::At`]: Position::At
+    pub fn move_nodes_strict( &mut self, source: usize, destination: usize, position: usize ) -> Result<(), TreeError> {
+        self.reparent( source, destination, Position::At( position ) )
+    }
+
+    /// Move the subtree `node` to become a child of the node `new_parent`, at the position `at` of the `new_parent`
+    /// node's children.
+    /// 
+    /// The `new_parent` node must be able to have children. Also the `node` can't be the `new_parent` node, or an
+    /// ancestor of the `new_parent` node, as that would create a cycle. The `node` may already be a child of the
+    /// `new_parent` node, in which case the `node` is just repositioned.
+    /// 
+    /// For [`Position::At`], the position is the position of the `node` in the `new_parent` node's children after the
+    /// move, and must be in the range of 0 to the number of children (excluding the `node` itself).
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{ Tree, ALLOW_CHILDREN, ALLOW_DATA, Position };
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 338, ALLOW_CHILDREN, None, None, ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None, ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None, ).ok();
+    /// tree.insert( 1, ALLOW_DATA, None, None, ).ok();
+    /// tree.reparent( 3, 2, Position::Front ).ok();
+    /// assert_eq!( tree.parent( 3 ).unwrap(), 2, "Parent of node 3 is 2." );
+    /// assert!( tree.reparent( 1, 1, Position::Back ).is_err(), "Node 1 can't be its own parent." );
+    /// ```
+    /// 
+    /// [`Position::At`]: Position::At
+    pub fn reparent( &mut self, node: usize, new_parent: usize, at: Position ) -> Result<(), TreeError> {
+        self.can_move( node, new_parent )?;
+        let old_parent = self.node( node ).unwrap().parent;
+        let mut length = self.children( new_parent )?.len();
+        if old_parent == Some( new_parent ) {
+            length -= 1;
+        }
+        let position = match at {
+            Position::Front => 0,
+            Position::Back => length,
+            Position::At( position ) => {
+                if position > length {
+                    return Err( TreeError::ExceedsChildren( position, new_parent ) );
+                }
+                position
+            }
+        };
+
+        // Remove node from its parent's children. The root node is never moved, as it is an ancestor of every node.
+        let parent = old_parent.unwrap();
+        {
+            let Some( parent_node ) = self.node_mut( parent ) else {
+                return Err( TreeError::RetrievingNode( parent ) )
+            };
+            let children = parent_node.children.as_mut().unwrap();
+            let Some( _position ) = children.iter().position( |&x| x == node ) else {
+                return Err( TreeError::MissingInParent( node, parent ) ); // Serious integrity issue.
+            };
+            children.remove( _position );
+        }
+
+        // Add node to new parent's children.
+        self.node_mut( new_parent ).unwrap().children.as_mut().unwrap().insert( position, node );
+        self.node_mut( node ).unwrap().parent = Some( new_parent );
+        self.notify( Mutation::Move( node ) );
+        Ok( () )
+    }
+
+    /// Move the subtree `node` as [`reparent`] does, and return a [`MoveRecord`] of the node's previous parent and
+    /// position, for reversing the move with [`undo_move`].
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{ Tree, ALLOW_CHILDREN, ALLOW_DATA, Position };
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 338, ALLOW_CHILDREN, None, None, ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None, ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None, ).ok();
+    /// tree.insert( 1, ALLOW_DATA, None, None, ).ok();
+    /// let record = tree.reparent_recorded( 3, 2, Position::Front ).unwrap();
+    /// assert_eq!( record.old_parent, 1 );
+    /// assert_eq!( tree.parent( 3 ).unwrap(), 2 );
+    /// tree.undo_move( record ).ok();
+    /// assert_eq!( tree.parent( 3 ).unwrap(), 1 );
+    /// ```
+    /// 
+    /// [`reparent`]: Tree::reparent
+    /// [`MoveRecord`]: MoveRecord
+    /// [`undo_move`]: Tree::undo_move
+    pub fn reparent_recorded(
+        &mut self,
+        node: usize,
+        new_parent: usize,
+        at: Position
+    ) -> Result<MoveRecord, TreeError> {
+        self.can_move( node, new_parent )?;
+
+        // The root node can't be moved, as it is an ancestor of every node.
+        let record = MoveRecord {
+            node,
+            old_parent: self.parent( node )?,
+            old_position: self.child_position( node )?.unwrap(),
+        };
+        self.reparent( node, new_parent, at )?;
+        Ok( record )
+    }
+
+    /// Move the subtree `node` as [`reparent`] does, only when the `allow` closure accepts the move, else the error
+    /// [`TreeError::MoveRejected`] is returned.
+    /// 
+    /// The `allow` closure is passed the `node`, the `new_parent` and the tree, after the checks of [`can_move`] have
+    /// passed. Useful for enforcing invariants on where nodes may be placed, such as by node type.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{ Tree, ALLOW_CHILDREN, ALLOW_DATA, Position };
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 338, ALLOW_CHILDREN, None, None, ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None, ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None, ).ok();
+    /// tree.insert( 1, ALLOW_DATA, None, None, ).ok();
+    /// assert!( tree.reparent_if( 3, 2, Position::Back, |_, new_parent, _| new_parent != 2 ).is_err() );
+    /// tree.reparent_if( 3, 0, Position::Back, |_, new_parent, _| new_parent != 2 ).ok();
+    /// assert_eq!( tree.parent( 3 ).unwrap(), 0 );
+    /// ```
+    /// 
+    /// [`reparent`]: Tree::reparent
+    /// [`TreeError::MoveRejected`]: TreeError::MoveRejected
+    /// [`can_move`]: Tree::can_move
+    pub fn reparent_if<F>( &mut self, node: usize, new_parent: usize, at: Position, allow: F ) -> Result<(), TreeError>
+    where
+        F: Fn( usize, usize, &Tree ) -> bool
+    {
+        self.can_move( node, new_parent )?;
+        if !allow( node, new_parent, self ) {
+            return Err( TreeError::MoveRejected( node, new_parent ) );
+        }
+        self.reparent( node, new_parent, at )
+    }
+
+    /// Reverse a move recorded by [`reparent_recorded`], by moving the node back to its previous parent and position.
+    /// 
+    /// Moves must be undone in the reverse order that they were performed, else the previous position may no longer
+    /// be valid.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{ Tree, ALLOW_CHILDREN, ALLOW_DATA, Position };
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 338, ALLOW_CHILDREN, None, None, ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None, ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None, ).ok();
+    /// let record = tree.reparent_recorded( 1, 0, Position::Back ).unwrap();
+    /// assert_eq!( tree.children( 0 ).unwrap(), &vec![ 2, 1 ] );
+    /// tree.undo_move( record ).ok();
+    /// assert_eq!( tree.children( 0 ).unwrap(), &vec![ 1, 2 ] );
+    /// ```
+    /// 
+    /// [`reparent_recorded`]: Tree::reparent_recorded
+    pub fn undo_move( &mut self, record: MoveRecord ) -> Result<(), TreeError> {
+        self.reparent( record.node, record.old_parent, Position::At( record.old_position ) )
+    }
+
+    /// Check if the subtree `source` can be moved to become a child of the node `destination`, without moving it.
+    /// 
+    /// The same checks as [`move_nodes`] and [`reparent`] are performed, except for the position: the `source` node
+    /// must exist, the `destination` node must exist and be able to have children, and the `source` node can't be the
+    /// `destination` node or an ancestor of the `destination` node.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 338, ALLOW_CHILDREN, None, None, ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None, ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None, ).ok();
+    /// tree.insert( 1, ALLOW_DATA, None, None, ).ok();
+    /// assert!( tree.can_move( 3, 2 ).is_ok() );
+    /// assert!( tree.can_move( 1, 3 ).is_err(), "Node 3 can't have children." );
+    /// ```
+    /// 
+    /// [`move_nodes`]: Tree::move_nodes
+    /// [`reparent`]: Tree::reparent
+    pub fn can_move( &self, source: usize, destination: usize ) -> Result<(), TreeError> {
+        if !self.exists( source ) {
+            return Err( TreeError::RetrievingNode( source ) );
+        }
+        self.children( destination )?;
+
+        // Check that source is not destination, or an ancestor of destination.
+        if source == destination || self.is_ancestor_of( destination, source )? {
+            return Err( TreeError::IsAncestorOf( destination, source ) );
+        }
+        Ok( () )
+    }
+
+    /// Move the subtree addressed by the position path `source_path` to become a child of the node addressed by the
+    /// position path `dest_path`, at the position `at` of that node's children.
+    /// 
+    /// Both position paths are descended from the root node (see [`descend_path`]), and are resolved before the move.
+    /// The move is performed by [`reparent`], thus the same checks are performed. An empty tree returns the error
+    /// [`TreeError::NoRootNode`].
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{ Tree, ALLOW_CHILDREN, ALLOW_DATA, Position };
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 338, ALLOW_CHILDREN, None, None, ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None, ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None, ).ok();
+    /// tree.insert( 1, ALLOW_DATA, None, None, ).ok();
+    /// tree.move_by_paths( &[ 0, 0 ], &[ 1 ], Position::Back ).ok();
+    /// assert_eq!( tree.position_path( 3 ).unwrap(), vec![ 1, 0 ] );
+    /// ```
+    /// 
+    /// [`descend_path`]: Tree::descend_path
+    /// [`reparent`]: Tree::reparent
+    /// [`TreeError::NoRootNode`]: TreeError::NoRootNode
+    pub fn move_by_paths(
+        &mut self,
+        source_path: &[usize],
+        dest_path: &[usize],
+        at: Position
+    ) -> Result<(), TreeError> {
+        let Some( root ) = self.root else {
+            return Err( TreeError::NoRootNode )
+        };
+        let node = self.descend_path( root, source_path )?;
+        let new_parent = self.descend_path( root, dest_path )?;
+        self.reparent( node, new_parent, at )
+    }
+
+    /// Swap the subtrees `a` and `b`, where each node takes the other node's position within its parent's children.
+    /// 
+    /// The entire subtrees are moved, that is the nodes keep their children. Neither node can be an ancestor of the
+    /// other node (this includes the root node), as that would create a cycle. Swapping a node with itself does
+    /// nothing.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 338, ALLOW_CHILDREN, None, None, ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None, ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None, ).ok();
+    /// tree.insert( 1, ALLOW_CHILDREN, None, None, ).ok();
+    /// tree.insert( 3, ALLOW_DATA, None, None, ).ok();
+    /// tree.insert( 2, ALLOW_DATA, None, None, ).ok();
+    /// tree.swap_subtrees( 3, 5 ).ok();
+    /// assert_eq!( tree.children( 1 ).unwrap(), &vec![ 5 ] );
+    /// assert_eq!( tree.children( 2 ).unwrap(), &vec![ 3 ] );
+    /// assert_eq!( tree.children( 3 ).unwrap(), &vec![ 4 ], "Node 3 keeps its child." );
+    /// assert!( tree.swap_subtrees( 2, 4 ).is_err(), "Node 2 is an ancestor of node 4." );
+    /// ```
+    pub fn swap_subtrees( &mut self, a: usize, b: usize ) -> Result<(), TreeError> {
+        if !self.exists( a ) {
+            return Err( TreeError::RetrievingNode( a ) );
+        }
+        if !self.exists( b ) {
+            return Err( TreeError::RetrievingNode( b ) );
+        }
+        if a == b {
+            return Ok( () );
+        }
+        if self.is_ancestor_of( b, a )? {
+            return Err( TreeError::IsAncestorOf( b, a ) );
+        }
+        if self.is_ancestor_of( a, b )? {
+            return Err( TreeError::IsAncestorOf( a, b ) );
+        }
+
+        // Neither node is the root node, as the root node is an ancestor of every node.
+        let parent_a = self.parent( a )?;
+        let parent_b = self.parent( b )?;
+        let position_a = self.child_position( a )?.unwrap();
+        let position_b = self.child_position( b )?.unwrap();
+        self.node_mut( parent_a ).unwrap().children.as_mut().unwrap()[ position_a ] = b;
+        self.node_mut( parent_b ).unwrap().children.as_mut().unwrap()[ position_b ] = a;
+        self.node_mut( a ).unwrap().parent = Some( parent_b );
+        self.node_mut( b ).unwrap().parent = Some( parent_a );
+        self.notify( Mutation::Move( a ) );
+        self.notify( Mutation::Move( b ) );
+        Ok( () )
+    }
+
+    /// Reorder the children of the specified node `node_index`, where `new_order` is the new order of the children.
+    /// 
+    /// The `new_order` must contain exactly the current children of the node, else the error
+    /// [`TreeError::NotAPermutation`] is returned. Only the order of the children is changed.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 338, ALLOW_CHILDREN, None, None, ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None, ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None, ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None, ).ok();
+    /// tree.set_child_order( 0, &[ 3, 1, 2 ] ).ok();
+    /// assert_eq!( tree.children( 0 ).unwrap(), &vec![ 3, 1, 2 ] );
+    /// assert!( tree.set_child_order( 0, &[ 3, 1 ] ).is_err(), "Node 2 is missing." );
+    /// ```
+    /// 
+    /// [`TreeError::NotAPermutation`]: TreeError::NotAPermutation
+    pub fn set_child_order( &mut self, node_index: usize, new_order: &[usize] ) -> Result<(), TreeError> {
+        let mut current = self.children( node_index )?.clone();
+        let mut sorted = new_order.to_vec();
+        current.sort_unstable();
+        sorted.sort_unstable();
+        if current != sorted {
+            return Err( TreeError::NotAPermutation( node_index ) );
+        }
+        self.node_mut( node_index ).unwrap().children = Some( new_order.to_vec() );
+        self.notify( Mutation::Children( node_index ) );
+        Ok( () )
+    }
+
+    /// Swap the children at the positions `pos_a` and `pos_b` of the specified node `parent`.
+    /// 
+    /// Both positions must be less than the number of children, else the error [`TreeError::ExceedsChildren`] is
+    /// returned. Only the order of the children is changed.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 338, ALLOW_CHILDREN, None, None, ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None, ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None, ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None, ).ok();
+    /// tree.swap_children( 0, 0, 2 ).ok();
+    /// assert_eq!( tree.children( 0 ).unwrap(), &vec![ 3, 2, 1 ] );
+    /// assert!( tree.swap_children( 0, 0, 3 ).is_err(), "Position 3 is out of range." );
+    /// ```
+    /// 
+    /// [`TreeError::ExceedsChildren`]: TreeError::ExceedsChildren
+    pub fn swap_children( &mut self, parent: usize, pos_a: usize, pos_b: usize ) -> Result<(), TreeError> {
+        let length = self.children( parent )?.len();
+        if let Some( position ) = [ pos_a, pos_b ].into_iter().find( |&position| position >= length ) {
+            return Err( TreeError::ExceedsChildren( position, parent ) );
+        }
+        self.node_mut( parent ).unwrap().children.as_mut().unwrap().swap( pos_a, pos_b );
+        self.notify( Mutation::Children( parent ) );
+        Ok( () )
+    }
+
+    // -- information methods --
+
+    /// Check if `node_index` exists in the tree.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 53, ALLOW_CHILDREN | ALLOW_DATA, None, None ).ok();
+    /// assert!( tree.exists( 0 ) );
+    /// assert!( !tree.exists( 1 ) );
+    /// ```
+    pub fn exists( &self, node_index: usize ) -> bool {
+        matches!( self.nodes.get( node_index ), Some( Some( _ ) ) )
+    }
+
+    /// Check if `node_index` is an empty node (deleted/taken) within the internal vector of nodes, which will be reused
+    /// when creating nodes.
+    /// 
+    /// Unlike [`exists`], `false` is returned for an index beyond the length of the internal vector.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 53, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// tree.delete( 1 ).ok();
+    /// assert!( tree.is_hole( 1 ) );
+    /// assert!( !tree.is_hole( 0 ) );
+    /// assert!( !tree.is_hole( 2 ) );
+    /// ```
+    /// 
+    /// [`exists`]: Tree::exists
+    pub fn is_hole( &self, node_index: usize ) -> bool {
+        matches!( self.nodes.get( node_index ), Some( None ) )
+    }
+
+    /// Check the integrity of the links of the specified node `node_index`, without checking the entire tree.
+    /// 
+    /// The node's parent (if any) must have the node in its children, and each of the node's children must have the
+    /// node as its parent. The root node must not have a parent. The error of the first problem found is returned.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 53, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// assert!( tree.validate_node( 0 ).is_ok() );
+    /// assert!( tree.validate_node( 1 ).is_ok() );
+    /// assert!( tree.validate_node( 2 ).is_err(), "Node 2 does not exist." );
+    /// ```
+    pub fn validate_node( &self, node_index: usize ) -> Result<(), TreeError> {
+        let Some( index_node ) = self.node( node_index ) else {
+            return Err( TreeError::RetrievingNode( node_index ) )
+        };
+        if let Some( parent ) = index_node.parent {
+            if Some( node_index ) == self.root {
+                return Err( TreeError::RootHasNoParent( node_index ) );
+            }
+            let Some( parent_node ) = self.node( parent ) else {
+                return Err( TreeError::RetrievingNode( parent ) )
+            };
+            if !parent_node.children.as_ref().map_or( false, |children| children.contains( &node_index ) ) {
+                return Err( TreeError::MissingInParent( node_index, parent ) );
+            }
+        }
+        if let Some( children ) = index_node.children.as_ref() {
+            for &child in children {
+                let Some( child_node ) = self.node( child ) else {
+                    return Err( TreeError::RetrievingNode( child ) )
+                };
+                if child_node.parent != Some( node_index ) {
+                    return Err( TreeError::InvalidEdge( child ) );
+                }
+            }
+        }
+        Ok( () )
+    }
+
+    /// Obtain reference to the node type for the specified node `node_index`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 514, ALLOW_CHILDREN | ALLOW_DATA, Some( Box::new( "node type 2".to_string() ) ), None ).ok();
+    /// let type_any_ref = tree.node_type( 0 ).ok().unwrap().as_ref().unwrap();
+    /// let type_usize = type_any_ref.downcast_ref::<String>().unwrap();
+    /// assert_eq!( *type_usize, "node type 2" );
+    /// ```
+    pub fn node_type( &self, node_index: usize ) -> Result<&Option<Box<dyn Any>>, TreeError> {
+        let Some( index_node ) = self.node( node_index ) else {
+            return Err( TreeError::RetrievingNode( node_index ) )
+        };
+        Ok( &index_node.node_type )
+    }
+
+    /// Obtain reference to the node type, downcast to the type `T`, for the specified node `node_index`.
+    /// 
+    /// `None` is returned when the node has no node type, and the error [`TreeError::DowncastFailed`] is returned when
+    /// the node type is not of the type `T`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 514, ALLOW_CHILDREN | ALLOW_DATA, Some( Box::new( "node type 2".to_string() ) ), None ).ok();
+    /// let node_type = tree.node_type_downcast_ref::<String>( 0 ).unwrap().unwrap();
+    /// assert_eq!( *node_type, "node type 2" );
+    /// assert!( tree.node_type_downcast_ref::<usize>( 0 ).is_err(), "Node type is not `usize`." );
+    /// ```
+    /// 
+    /// [`TreeError::DowncastFailed`]: TreeError::DowncastFailed
+    pub fn node_type_downcast_ref<T: Any>( &self, node_index: usize ) -> Result<Option<&T>, TreeError> {
+        let Some( node_type ) = self.node_type( node_index )? else {
+            return Ok( None )
+        };
+        let Some( node_type ) = node_type.downcast_ref::<T>() else {
+            return Err( TreeError::DowncastFailed( node_index ) )
+        };
+        Ok( Some( node_type ) )
+    }
+
+    /// Take the node type out of the specified node `node_index`, leaving the node type as `None`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 514, ALLOW_CHILDREN | ALLOW_DATA, Some( Box::new( "node type 2".to_string() ) ), None ).ok();
+    /// let node_type = tree.take_node_type( 0 ).unwrap().unwrap().downcast::<String>().ok().unwrap();
+    /// assert_eq!( *node_type, "node type 2" );
+    /// assert!( tree.node_type( 0 ).unwrap().is_none(), "Node type is now `None`." );
+    /// ```
+    pub fn take_node_type( &mut self, node_index: usize ) -> Result<Option<Box<dyn Any>>, TreeError> {
+        let Some( index_node ) = self.node_mut( node_index ) else {
+            return Err( TreeError::RetrievingNode( node_index ) )
+        };
+        let node_type = index_node.node_type.take();
+        self.notify( Mutation::Update( node_index ) );
+        Ok( node_type )
+    }
+
+    /// Obtain reference to the node's features for the specified node `node_index`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 16, ALLOW_CHILDREN | ALLOW_DATA, None, None ).ok();
+    /// let features_ref = tree.features( 0 );
+    /// let features = *features_ref.as_ref().unwrap();
+    /// assert_eq!( features & ALLOW_CHILDREN, ALLOW_CHILDREN );
+    /// assert_eq!( features & ALLOW_DATA, ALLOW_DATA );
+    /// ```
+    pub fn features( &self, node_index: usize ) -> Result<&u8, TreeError> {
+        let Some( index_node ) = self.node( node_index ) else {
+            return Err( TreeError::RetrievingNode( node_index ) )
+        };
+        Ok( &index_node.features )
+    }
+
+    /// Obtain reference to the node's immediate parent for the specified node `node_index`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 23, ALLOW_CHILDREN | ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// assert_eq!( tree.parent( 1 ).ok(), Some( 0 ), "Parent is root node." );
+    /// ```
+    pub fn parent( &self, node_index: usize ) -> Result<usize, TreeError> {
+        if Some( node_index ) == self.root {
+            return Err( TreeError::RootHasNoParent( node_index ) );
+        }
+        let Some( index_node ) = self.node( node_index ) else {
+            return Err( TreeError::RetrievingNode( node_index ) )
+        };
+        Ok( *index_node.parent.as_ref().unwrap() )
+    }
+
+    /// Obtain the position of the specified node `node_index` within its parent's children.
+    /// 
+    /// The root node has no position, thus `None` is returned for the root node instead of the error
+    /// [`TreeError::RootHasNoParent`] that the [`parent`] method returns.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 23, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// assert_eq!( tree.child_position( 2 ).unwrap(), Some( 1 ), "Node 2 is the second child." );
+    /// assert_eq!( tree.child_position( 0 ).unwrap(), None, "Root node has no position." );
+    /// ```
+    /// 
+    /// [`TreeError::RootHasNoParent`]: TreeError::RootHasNoParent
+    /// [`parent`]: Tree::parent
+    pub fn child_position( &self, node_index: usize ) -> Result<Option<usize>, TreeError> {
+        let Some( index_node ) = self.node( node_index ) else {
+            return Err( TreeError::RetrievingNode( node_index ) )
+        };
+        let Some( parent ) = index_node.parent else {
+            return Ok( None )
+        };
+        let children = self.children( parent )?;
+        let Some( position ) = children.iter().position( |&x| x == node_index ) else {
+            return Err( TreeError::MissingInParent( node_index, parent ) ); // Serious integrity issue.
+        };
+        Ok( Some( position ) )
+    }
+
+    /// Determine if a node `is_ancestor` is an ancestor of the specified node `node_index`. This method will iterate
+    /// through the parents until the root node.
+    /// 
+    /// `true` is returned if the node is found to be ancestor of the specified node, else `false` is returned.
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 338, ALLOW_CHILDREN, None, None, ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None, ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None, ).ok();
+    /// let last = tree.insert( 1, ALLOW_CHILDREN, None, None, ).ok().unwrap();
+    /// assert_eq!( last, 3 );
+    /// let mut result = tree.is_ancestor_of( 3, 0 ).unwrap();
+    /// assert!( result, "Root is grandparent of node 3." );
+    /// result = tree.is_ancestor_of( 3, 2 ).unwrap();
+    /// assert!( !result, "Node 2 is not a parent of node 3." );
+    /// ```
+    pub fn is_ancestor_of( &self, mut node_index: usize, is_ancestor: usize ) -> Result<bool, TreeError> {
+        loop {
+            let parent = match self.parent( node_index ) {
+                Ok( result ) => result,
+                Err( error ) => return match error {
+                    TreeError::RootHasNoParent( _ ) => Ok( false ),
+                    _ => Err( error )
+                }
+            };
+            if parent == is_ancestor {
+                return Ok( true );
+            }
+            node_index = parent;
+        }
+    }
+
+    /// Find the nearest ancestor of the specified node `node_index` for which the `predicate` returns `true`. The
+    /// ancestors are searched from the node's parent up to the root node.
+    /// 
+    /// The `predicate` is passed the index of the ancestor and the tree. `None` is returned if no ancestor matches.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 338, ALLOW_CHILDREN, None, None, ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, Some( Box::new( "Statement".to_string() ) ), None, ).ok();
+    /// tree.insert( 1, ALLOW_CHILDREN, None, None, ).ok();
+    /// tree.insert( 2, ALLOW_DATA, None, None, ).ok();
+    /// let statement = tree.nearest_ancestor_with( 3, |index, tree| {
+    ///     tree.node_type( index ).unwrap().as_ref().map_or( false, |node_type| {
+    ///         node_type.downcast_ref::<String>().map_or( false, |node_type| node_type == "Statement" )
+    ///     } )
+    /// } ).unwrap();
+    /// assert_eq!( statement, Some( 1 ), "Node 1 is the nearest statement." );
+    /// ```
+    pub fn nearest_ancestor_with<F>( &self, node_index: usize, predicate: F ) -> Result<Option<usize>, TreeError>
+    where
+        F: Fn( usize, &Tree ) -> bool
+    {
+        let path = self.path_to_root( node_index )?;
+        Ok( path.into_iter().skip( 1 ).find( |&index| predicate( index, self ) ) )
+    }
+
+    /// Obtain the ancestor of the specified node `node_index` at the depth `target_depth`, where the root node is at
+    /// depth 0. The node itself is returned when `target_depth` is the depth of the node.
+    /// 
+    /// `None` is returned when `target_depth` exceeds the depth of the node.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 338, ALLOW_CHILDREN, None, None, ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None, ).ok();
+    /// tree.insert( 1, ALLOW_DATA, None, None, ).ok();
+    /// assert_eq!( tree.ancestor_at_depth( 2, 1 ).unwrap(), Some( 1 ) );
+    /// assert_eq!( tree.ancestor_at_depth( 2, 2 ).unwrap(), Some( 2 ) );
+    /// assert_eq!( tree.ancestor_at_depth( 2, 3 ).unwrap(), None );
+    /// ```
+    pub fn ancestor_at_depth( &self, node_index: usize, target_depth: usize ) -> Result<Option<usize>, TreeError> {
+        let path = self.path_to_root( node_index )?;
+        let depth = path.len() - 1;
+        if target_depth > depth {
+            return Ok( None );
+        }
+        Ok( Some( path[ depth - target_depth ] ) )
+    }
+
+    /// Search the tree in pre-order (depth first) from the root node for the first node for which the `predicate`
+    /// returns `true`, and obtain the path from the root node down to the node (inclusive of both).
+    /// 
+    /// `None` is returned if no node matches.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 338, ALLOW_CHILDREN, None, None, ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None, ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None, ).ok();
+    /// tree.insert( 2, ALLOW_DATA, None, None, ).ok();
+    /// let path = tree.find_path_to( |index, tree| tree.data_ref( index ).is_ok() );
+    /// assert_eq!( path, Some( vec![ 0, 2, 3 ] ) );
+    /// ```
+    pub fn find_path_to<F>( &self, predicate: F ) -> Option<Vec<usize>>
+    where
+        F: Fn( usize, &Tree ) -> bool
+    {
+        let index = self.subtree( self.root? ).ok()?.into_iter().find( |&index| predicate( index, self ) )?;
+        let mut path = self.path_to_root( index ).ok()?;
+        path.reverse();
+        Some( path )
+    }
+
+    /// Search the subtree `start` in pre-order (depth first) for a node, where the `visit` closure decides for each
+    /// visited node whether it is the node being searched for, or whether its children are to be searched or skipped.
+    /// 
+    /// The first node for which `visit` returns [`Visit::Found`] is returned, or `None` when no node was found. The
+    /// descendants of a node for which `visit` returns [`Visit::Skip`] are never visited, making this more efficient
+    /// than searching the whole subtree when large regions of the tree can be skipped.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA, Visit};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 338, ALLOW_CHILDREN, None, None, ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None, ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None, ).ok();
+    /// tree.insert( 1, ALLOW_DATA, None, None, ).ok();
+    /// tree.insert( 2, ALLOW_DATA, None, None, ).ok();
+    /// let found = tree.search_pruned( 0, |index, tree| {
+    ///     if index == 1 {
+    ///         Visit::Skip
+    ///     } else if tree.data_ref( index ).is_ok() {
+    ///         Visit::Found
+    ///     } else {
+    ///         Visit::Descend
+    ///     }
+    /// } );
+    /// assert_eq!( found, Some( 4 ) );
+    /// ```
+    /// 
+    /// [`Visit::Found`]: Visit::Found
+    /// [`Visit::Skip`]: Visit::Skip
+    pub fn search_pruned<F>( &self, start: usize, mut visit: F ) -> Option<usize>
+    where
+        F: FnMut( usize, &Tree ) -> Visit
+    {
+        self.node( start )?;
+        let mut stack = vec![ start ];
+        while let Some( index ) = stack.pop() {
+            match visit( index, self ) {
+                Visit::Found => return Some( index ),
+                Visit::Skip => {},
+                Visit::Descend => {
+                    if let Some( children ) = self.node( index )?.children.as_ref() {
+                        stack.extend( children.iter().rev() );
+                    }
+                },
+            }
+        }
+        None
+    }
+
+    /// Obtain the path between the nodes `from` and `to`, by walking up from `from` to the lowest common ancestor of
+    /// the nodes, and then down to `to`.
+    /// 
+    /// The path includes both `from` and `to`, and the lowest common ancestor only once.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 338, ALLOW_CHILDREN, None, None, ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None, ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None, ).ok();
+    /// tree.insert( 1, ALLOW_DATA, None, None, ).ok();
+    /// tree.insert( 2, ALLOW_DATA, None, None, ).ok();
+    /// assert_eq!( tree.path_between( 3, 4 ).unwrap(), vec![ 3, 1, 0, 2, 4 ] );
+    /// assert_eq!( tree.path_between( 3, 0 ).unwrap(), vec![ 3, 1, 0 ] );
+    /// ```
+    pub fn path_between( &self, from: usize, to: usize ) -> Result<Vec<usize>, TreeError> {
+        let mut from_path = self.path_to_root( from )?;
+        let mut to_path = self.path_to_root( to )?;
+
+        // Remove the common ancestors, except the lowest common ancestor.
+        while from_path.len() > 1
+            && to_path.len() > 1
+            && from_path[ from_path.len() - 2 ] == to_path[ to_path.len() - 2 ]
+        {
+            from_path.pop();
+            to_path.pop();
+        }
+        to_path.pop();
+        from_path.extend( to_path.iter().rev() );
+        Ok( from_path )
+    }
+
+    /// Obtain reference to the node children for the specified node `node_index`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 624, ALLOW_CHILDREN | ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// let children = tree.children( 0 ).ok().unwrap();
+    /// assert_eq!( children.len(), 1, "Has 1 child." );
+    /// ```
+    pub fn children( &self, node_index: usize ) -> Result<&Vec<usize>, TreeError> {
+        let Some( index_node ) = self.node( node_index ) else {
+            return Err( TreeError::RetrievingNode( node_index ) )
+        };
+        if index_node.features & ALLOW_CHILDREN != ALLOW_CHILDREN {
+            return Err( TreeError::NoChildrenAllowed( node_index ) );
+        }
+        Ok( index_node.children.as_ref().unwrap() )
+    }
+
+    /// Obtain the number of children (degree) of the specified node `node_index`, which must be able to have children.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 87, ALLOW_CHILDREN, None, None ).ok();
+    /// assert_eq!( tree.children_len( 0 ).unwrap(), 0, "Has no children." );
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// assert_eq!( tree.children_len( 0 ).unwrap(), 1, "Has 1 child." );
+    /// assert!( tree.children_len( 1 ).is_err(), "Node 1 can't have children." );
+    /// ```
+    pub fn children_len( &self, node_index: usize ) -> Result<usize, TreeError> {
+        Ok( self.children( node_index )?.len() )
+    }
+
+    /// Iterate over the indices of the children of the specified node `node_index` in order, which must be able to have
+    /// children.
+    /// 
+    /// The indices are yielded by value, allowing iterator adaptors to be chained directly.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 87, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// let data_children = tree.children_iter( 0 ).unwrap()
+    ///     .filter( |&index| tree.data_ref( index ).is_ok() )
+    ///     .collect::<Vec<usize>>();
+    /// assert_eq!( data_children, vec![ 1, 3 ] );
+    /// assert!( tree.children_iter( 1 ).is_err(), "Node 1 can't have children." );
+    /// ```
+    pub fn children_iter( &self, node_index: usize ) -> Result<impl Iterator<Item = usize> + '_, TreeError> {
+        Ok( self.children( node_index )?.iter().copied() )
+    }
+
+    /// Obtain all the children of the parent of the specified node `node_index` in order, including the node itself.
+    /// 
+    /// The root node has no parent, thus a vector containing only the root node is returned for the root node.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 624, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// assert_eq!( tree.iter_siblings_including( 2 ).unwrap(), vec![ 1, 2 ] );
+    /// assert_eq!( tree.iter_siblings_including( 0 ).unwrap(), vec![ 0 ] );
+    /// ```
+    pub fn iter_siblings_including( &self, node_index: usize ) -> Result<Vec<usize>, TreeError> {
+        let Some( index_node ) = self.node( node_index ) else {
+            return Err( TreeError::RetrievingNode( node_index ) )
+        };
+        let Some( parent ) = index_node.parent else {
+            return Ok( vec![ node_index ] )
+        };
+        Ok( self.children( parent )?.clone() )
+    }
+
+    /// Convenience method to obtain the first child of the node `node_index`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 624, ALLOW_CHILDREN | ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// let first = tree.first( 0 ).ok().unwrap();
+    /// assert_eq!( first, 1, "First child is index 1." );
+    /// ```
+    pub fn first( &self, node_index: usize ) -> Result<usize, TreeError> {
+        let children = self.children( node_index )?;
+        let Some( index ) = children.first() else {
+            return Err( TreeError::NoChildrenFound( node_index ) )
+        };
+        Ok( *index )
+    }
+
+    /// Convenience method to obtain the last child of the node `node_index`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 624, ALLOW_CHILDREN | ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// let last = tree.last( 0 ).ok().unwrap();
+    /// assert_eq!( last, 1, "Last child is index 1." );
+    /// ```
+    pub fn last( &self, node_index: usize ) -> Result<usize, TreeError> {
+        let children = self.children( node_index )?;
+        let Some( index ) = children.last() else {
+            return Err( TreeError::NoChildrenFound( node_index ) )
+        };
+        Ok( *index )
+    }
+
+    /// Convenience method to obtain the nth child `position` of the node `node_index`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 624, ALLOW_CHILDREN | ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// let child = tree.child( 0, 0 ).ok().unwrap();
+    /// assert_eq!( child, 1, "Has 1 child with index 1." );
+    /// ```
+    pub fn child( &self, node_index: usize, position: usize ) -> Result<usize, TreeError> {
+        let children = self.children( node_index )?;
+        let Some( index ) = children.get( position ) else {
+            return Err( TreeError::NoChildrenFound( node_index ) )
+        };
+        Ok( *index )
+    }
+
+    /// Descend from the node `start` by taking the child at each of the `positions` in turn, and return the index of
+    /// the final node. An empty `positions` returns `start`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 624, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 1, ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 1, ALLOW_DATA, None, None ).ok();
+    /// assert_eq!( tree.descend_path( 0, &[ 0, 1 ] ).unwrap(), 3 );
+    /// assert!( tree.descend_path( 0, &[ 0, 2 ] ).is_err(), "Node 1 has no third child." );
+    /// ```
+    pub fn descend_path( &self, start: usize, positions: &[usize] ) -> Result<usize, TreeError> {
+        if !self.exists( start ) {
+            return Err( TreeError::RetrievingNode( start ) );
+        }
+        let mut node_index = start;
+        for position in positions {
+            node_index = self.child( node_index, *position )?;
+        }
+        Ok( node_index )
+    }
+
+    /// Obtain the address of the specified node `node_index` as the sequence of child positions from the root node
+    /// down to the node. The root node has an empty address.
+    /// 
+    /// The address is the inverse of [`descend_path`] starting at the root node.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 624, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 1, ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 1, ALLOW_DATA, None, None ).ok();
+    /// assert_eq!( tree.position_path( 3 ).unwrap(), vec![ 0, 1 ] );
+    /// assert!( tree.position_path( 0 ).unwrap().is_empty(), "Root node has an empty address." );
+    /// ```
+    /// 
+    /// [`descend_path`]: Tree::descend_path
+    pub fn position_path( &self, node_index: usize ) -> Result<Vec<usize>, TreeError> {
+        let mut positions = Vec::<usize>::new();
+        for index in self.path_to_root( node_index )? {
+            if let Some( position ) = self.child_position( index )? {
+                positions.push( position );
+            }
+        }
+        positions.reverse();
+        Ok( positions )
+    }
+
+    /// Obtain the length of the common prefix of the position paths (see [`position_path`]) of the nodes `a` and `b`,
+    /// which is the depth of the lowest common ancestor of the nodes.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 624, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 1, ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 1, ALLOW_DATA, None, None ).ok();
+    /// assert_eq!( tree.common_prefix_length( 2, 3 ).unwrap(), 1, "Lowest common ancestor is node 1." );
+    /// assert_eq!( tree.common_prefix_length( 2, 0 ).unwrap(), 0, "Lowest common ancestor is root." );
+    /// ```
+    /// 
+    /// [`position_path`]: Tree::position_path
+    pub fn common_prefix_length( &self, a: usize, b: usize ) -> Result<usize, TreeError> {
+        let path_a = self.position_path( a )?;
+        let path_b = self.position_path( b )?;
+        Ok( path_a.iter().zip( path_b.iter() ).take_while( |( a, b )| a == b ).count() )
+    }
+
+    /// Find the first child of the node `parent` whose node type is of the type `T` and is equal to `target`. Children
+    /// without a node type, or with a node type of a different type, are skipped.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 624, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, Some( Box::new( 7u8 ) ), None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, Some( Box::new( "Leaf".to_string() ) ), None ).ok();
+    /// let child = tree.find_child_by_type( 0, &"Leaf".to_string() ).unwrap();
+    /// assert_eq!( child, Some( 2 ), "Found child is index 2." );
+    /// ```
+    pub fn find_child_by_type<T: Any + PartialEq>(
+        &self,
+        parent: usize,
+        target: &T
+    ) -> Result<Option<usize>, TreeError> {
+        for child in self.children( parent )? {
+            let Some( node ) = self.node( *child ) else {
+                return Err( TreeError::RetrievingNode( *child ) )
+            };
+            if let Some( node_type ) = node.node_type.as_ref().and_then( |node_type| node_type.downcast_ref::<T>() ) {
+                if node_type == target {
+                    return Ok( Some( *child ) );
+                }
+            }
+        }
+        Ok( None )
+    }
+
+    /// Obtain the depth of the specified node `node_index` from the root.
+    /// 
+    /// As the depth can't exceed the number of nodes, climbing more parents than the length of the internal vector of
+    /// nodes means the parents form a cycle (a serious integrity issue), and the error [`TreeError::CycleDetected`] is
+    /// returned instead of looping forever.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 72, ALLOW_CHILDREN | ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// let depth = tree.depth( 1 ).ok().unwrap();
+    /// assert_eq!( depth, 1, "Has 1 child." );
+    /// ```
+    /// 
+    /// [`TreeError::CycleDetected`]: TreeError::CycleDetected
+    pub fn depth( &self, mut node_index: usize ) -> Result<usize, TreeError> {
+        let mut depth = 0;
+        loop {
+            if let Some( node ) = self.node( node_index ) {
+                if let Some( parent ) = node.parent.as_ref() {
+                    node_index = *parent;
+                    depth += 1;
+                    if depth > self.nodes.len() {
+                        return Err( TreeError::CycleDetected( node_index ) ); // Serious integrity issue.
+                    }
+                }
+                else {
+                    return Ok( depth );
+                }
+            }
+            else {
+                return Err( TreeError::RetrievingNode( node_index ) );
+            }
+        }
+    }
+
+    /// Obtain the depth of every node in a single pass from the root node, indexed by the position of the node within
+    /// the internal vector of nodes. Empty nodes (deleted/taken) and nodes not reachable from the root node have `None`.
+    /// 
+    /// Useful when the depths of many nodes are required, instead of calling [`depth`] for each node.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 72, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 1, ALLOW_DATA, None, None ).ok();
+    /// tree.delete( 2 ).ok();
+    /// assert_eq!( tree.all_depths(), vec![ Some( 0 ), Some( 1 ), None, Some( 2 ) ] );
+    /// ```
+    /// 
+    /// [`depth`]: Tree::depth
+    pub fn all_depths( &self ) -> Vec<Option<usize>> {
+        let mut depths = vec![ None; self.nodes.len() ];
+        let Some( root ) = self.root else {
+            return depths;
+        };
+        let Ok( subtree ) = self.subtree( root ) else {
+            return depths;
+        };
+
+        // Visiting the nodes in pre-order ensures the parent's depth is known before its children's.
+        for index in subtree {
+            depths[ index ] = match self.node( index ).unwrap().parent {
+                Some( parent ) => depths[ parent ].map( |depth: usize| depth + 1 ),
+                None => Some( 0 )
+            };
+        }
+        depths
+    }
+
+    /// Obtain all the leaf nodes (nodes without children) of the subtree `node_index` in pre-order, including the node
+    /// itself if it is a leaf node.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 72, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 1, ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 1, ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// assert_eq!( tree.subtree_leaves( 1 ).unwrap(), vec![ 2, 3 ] );
+    /// assert_eq!( tree.subtree_leaves( 4 ).unwrap(), vec![ 4 ] );
+    /// ```
+    pub fn subtree_leaves( &self, node_index: usize ) -> Result<Vec<usize>, TreeError> {
+        Ok(
+            self.subtree( node_index )?
+                .into_iter()
+                .filter( |&index| {
+                    self.node( index ).unwrap().children.as_ref().map_or( true, |children| children.is_empty() )
+                } )
+                .collect()
+        )
+    }
+
+    /// Obtain the node following the specified node `node_index` in pre-order (depth first), being the first child,
+    /// else the next sibling, else the next sibling of the nearest ancestor having one.
+    /// 
+    /// `None` is returned for the last node in pre-order.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 72, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 1, ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// assert_eq!( tree.next_preorder( 1 ).unwrap(), Some( 2 ) );
+    /// assert_eq!( tree.next_preorder( 2 ).unwrap(), Some( 3 ) );
+    /// assert_eq!( tree.next_preorder( 3 ).unwrap(), None );
+    /// ```
+    pub fn next_preorder( &self, mut node_index: usize ) -> Result<Option<usize>, TreeError> {
+        let Some( index_node ) = self.node( node_index ) else {
+            return Err( TreeError::RetrievingNode( node_index ) )
+        };
+        if let Some( &child ) = index_node.children.as_ref().and_then( |children| children.first() ) {
+            return Ok( Some( child ) );
+        }
+        while let Some( position ) = self.child_position( node_index )? {
+            let parent = self.node( node_index ).unwrap().parent.unwrap();
+            if let Some( &sibling ) = self.children( parent )?.get( position + 1 ) {
+                return Ok( Some( sibling ) );
+            }
+            node_index = parent;
+        }
+        Ok( None )
+    }
+
+    /// Obtain the node preceding the specified node `node_index` in pre-order (depth first), being the last leaf node
+    /// of the previous sibling, else the parent.
+    /// 
+    /// `None` is returned for the first node in pre-order (the root node).
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 72, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 1, ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// assert_eq!( tree.prev_preorder( 3 ).unwrap(), Some( 2 ) );
+    /// assert_eq!( tree.prev_preorder( 2 ).unwrap(), Some( 1 ) );
+    /// assert_eq!( tree.prev_preorder( 0 ).unwrap(), None );
+    /// ```
+    pub fn prev_preorder( &self, node_index: usize ) -> Result<Option<usize>, TreeError> {
+        let Some( position ) = self.child_position( node_index )? else {
+            return Ok( None )
+        };
+        let parent = self.node( node_index ).unwrap().parent.unwrap();
+        if position == 0 {
+            return Ok( Some( parent ) );
+        }
+        let sibling = self.child( parent, position - 1 )?;
+        Ok( Some( self.last_leaf( sibling )? ) )
+    }
+
+    /// Obtain the first leaf node (a node without children) of the subtree `node_index`, by descending through the
+    /// first children. This is the leftmost leaf node of the subtree. A leaf node returns itself.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 72, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 1, ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 1, ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// assert_eq!( tree.first_leaf( 0 ).unwrap(), 2 );
+    /// assert_eq!( tree.first_leaf( 4 ).unwrap(), 4 );
+    /// ```
+    pub fn first_leaf( &self, mut node_index: usize ) -> Result<usize, TreeError> {
+        loop {
+            let Some( index_node ) = self.node( node_index ) else {
+                return Err( TreeError::RetrievingNode( node_index ) )
+            };
+            match index_node.children.as_ref().and_then( |children| children.first() ) {
+                Some( &child ) => node_index = child,
+                None => return Ok( node_index )
+            }
+        }
+    }
+
+    /// Obtain the last leaf node (a node without children) of the subtree `node_index`, by descending through the last
+    /// children. This is the rightmost leaf node of the subtree. A leaf node returns itself.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 72, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 1, ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 1, ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// assert_eq!( tree.last_leaf( 0 ).unwrap(), 4 );
+    /// assert_eq!( tree.last_leaf( 1 ).unwrap(), 3 );
+    /// ```
+    pub fn last_leaf( &self, mut node_index: usize ) -> Result<usize, TreeError> {
+        loop {
+            let Some( index_node ) = self.node( node_index ) else {
+                return Err( TreeError::RetrievingNode( node_index ) )
+            };
+            match index_node.children.as_ref().and_then( |children| children.last() ) {
+                Some( &child ) => node_index = child,
+                None => return Ok( node_index )
+            }
+        }
+    }
+
+    /// Obtain the `n`th leaf node (a node without children) of the tree in pre-order (left to right), counting from 0.
+    /// 
+    /// `None` is returned when the tree has `n` or fewer leaf nodes.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 72, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 1, ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// assert_eq!( tree.nth_leaf( 0 ), Some( 2 ) );
+    /// assert_eq!( tree.nth_leaf( 1 ), Some( 3 ) );
+    /// assert_eq!( tree.nth_leaf( 2 ), None );
+    /// ```
+    pub fn nth_leaf( &self, n: usize ) -> Option<usize> {
+        self.subtree_leaves( self.root? ).ok()?.into_iter().nth( n )
+    }
+
+    /// Obtain the index and depth of the deepest leaf node (a node without children) of the tree. When several leaf
+    /// nodes are at the maximum depth, the first leaf node encountered in pre-order is returned.
+    /// 
+    /// `None` is returned for an empty tree.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 72, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 2, ALLOW_DATA, None, None ).ok();
+    /// assert_eq!( tree.deepest_leaf(), Some( ( 3, 2 ) ), "Node 3 is at depth 2." );
+    /// ```
+    pub fn deepest_leaf( &self ) -> Option<( usize, usize )> {
+        let mut deepest: Option<( usize, usize )> = None;
+        let mut stack = vec![ ( self.root?, 0 ) ];
+        while let Some( ( index, depth ) ) = stack.pop() {
+            let Some( node ) = self.node( index ) else {
+                continue;
+            };
+            match node.children.as_ref() {
+                Some( children ) if !children.is_empty() => {
+                    stack.extend( children.iter().rev().map( |&child| ( child, depth + 1 ) ) );
+                },
+                _ => {
+                    if deepest.map_or( true, |( _, deepest_depth )| depth > deepest_depth ) {
+                        deepest = Some( ( index, depth ) );
+                    }
+                }
+            }
+        }
+        deepest
+    }
+
+    /// Obtain the balance factor of the specified node `node_index`, being the height of the first child's subtree minus
+    /// the height of the last child's subtree. A leaf node has a height of 0.
+    /// 
+    /// For nodes with fewer than two children, the balance factor is 0.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 72, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 2, ALLOW_DATA, None, None ).ok();
+    /// assert_eq!( tree.balance_factor( 0 ).unwrap(), -1, "Right heavy." );
+    /// assert_eq!( tree.balance_factor( 2 ).unwrap(), 0, "Only 1 child." );
+    /// ```
+    pub fn balance_factor( &self, node_index: usize ) -> Result<isize, TreeError> {
+        let Some( index_node ) = self.node( node_index ) else {
+            return Err( TreeError::RetrievingNode( node_index ) )
+        };
+        let Some( children ) = index_node.children.as_ref() else {
+            return Ok( 0 )
+        };
+        if children.len() < 2 {
+            return Ok( 0 );
+        }
+        let first = self.height( *children.first().unwrap() )? as isize;
+        let last = self.height( *children.last().unwrap() )? as isize;
+        Ok( first - last )
+    }
+
+    /// Check if the tree is balanced, where for every node the heights of its children's subtrees differ by at most 1.
+    /// An empty tree, and a tree of only the root node, are balanced.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 72, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 2, ALLOW_DATA, None, None ).ok();
+    /// assert!( tree.is_balanced(), "Heights differ by 1." );
+    /// tree.insert( 2, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 4, ALLOW_DATA, None, None ).ok();
+    /// assert!( !tree.is_balanced(), "Heights differ by 2." );
+    /// ```
+    pub fn is_balanced( &self ) -> bool {
+        let Some( root ) = self.root else {
+            return true;
+        };
+        let Ok( heights ) = self.heights( root ) else {
+            return false;
+        };
+        for node in self.nodes.iter().flatten() {
+            let Some( children ) = node.children.as_ref() else {
+                continue;
+            };
+            let min = children.iter().map( |&child| heights[ child ] ).min();
+            let max = children.iter().map( |&child| heights[ child ] ).max();
+            if let ( Some( min ), Some( max ) ) = ( min, max ) {
+                if max - min > 1 {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    /// Get length of internal vector of nodes, including the empty nodes (deleted/taken).
+    /// 
+    /// For actual number of nodes in the tree, use [`count`] method.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 297, ALLOW_CHILDREN | ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// match tree.delete( 1 ) {
+    ///     Err( error ) => println!( "{}", error ),
+    ///     Ok( _ ) => println!( "Succeeded to delete node." )
+    /// }
     /// assert_eq!( tree.count(), 1, "Has 1 node." );
     /// assert_eq!( tree.len(), 2, "Internal vector is 2." );
     /// ```
-    /// 
-    /// [`count`]: Tree::count
-    pub fn len( &self ) -> usize {
-        self.nodes.len()
+    /// 
+    /// [`count`]: Tree::count
+    #[allow( clippy::len_without_is_empty )] // `len` is the internal vector length, not the node count.
+    pub fn len( &self ) -> usize {
+        self.nodes.len()
+    }
+
+    /// Obtain the number of nodes the internal vector of nodes can hold without being reallocated, including the empty
+    /// nodes (deleted/taken).
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 297, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.reserve_for( 10 );
+    /// assert!( tree.capacity() >= 11 );
+    /// ```
+    pub fn capacity( &self ) -> usize {
+        self.nodes.capacity()
+    }
+
+    /// Obtain the index the next created node will have, being the first empty node (deleted/taken) within the
+    /// internal vector of nodes, else the length of the internal vector.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// assert_eq!( tree.next_insert_index(), 0 );
+    /// tree.insert( 297, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// assert_eq!( tree.next_insert_index(), 3 );
+    /// tree.delete( 1 ).ok();
+    /// assert_eq!( tree.next_insert_index(), 1 );
+    /// ```
+    pub fn next_insert_index( &self ) -> usize {
+        match self.nodes.iter().position( |x| x.is_none() ) {
+            Some( position ) => position,
+            None => self.nodes.len(),
+        }
+    }
+
+    /// Obtain the indices of all the empty nodes (deleted/taken) within the internal vector of nodes, in ascending
+    /// order. As the empty nodes are reused when creating nodes, the first index is where the next node will be created.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 297, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// tree.delete( 2 ).ok();
+    /// tree.delete( 1 ).ok();
+    /// assert_eq!( tree.holes(), vec![ 1, 2 ] );
+    /// ```
+    pub fn holes( &self ) -> Vec<usize> {
+        self.nodes
+            .iter()
+            .enumerate()
+            .filter( |( _, node )| node.is_none() )
+            .map( |( index, _ )| index )
+            .collect()
+    }
+
+    /// Count the empty nodes (deleted/taken) within the internal vector of nodes, without obtaining their indices as
+    /// [`holes`] does. This is the difference between [`len`] and [`count`].
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 297, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// assert_eq!( tree.hole_count(), 0 );
+    /// tree.delete( 1 ).ok();
+    /// assert_eq!( tree.hole_count(), 1 );
+    /// ```
+    /// 
+    /// [`holes`]: Tree::holes
+    /// [`len`]: Tree::len
+    /// [`count`]: Tree::count
+    pub fn hole_count( &self ) -> usize {
+        self.len() - self.count()
+    }
+
+    /// Count the nodes of the tree.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 297, ALLOW_CHILDREN | ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// assert_eq!( tree.count(), 2, "Has 2 nodes." );
+    /// ```
+    pub fn count( &self ) -> usize {
+        self.nodes.iter().filter( |n| !n.is_none() ).count()
+    }
+
+    /// Obtain the number of descendants of every node in a single pass, indexed by the position of the node within the
+    /// internal vector of nodes. Empty nodes (deleted/taken) have `None`.
+    /// 
+    /// Useful when the descendant counts of many overlapping subtrees are required, as the counts are only computed
+    /// once.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 297, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 1, ALLOW_DATA, None, None ).ok();
+    /// tree.delete( 2 ).ok();
+    /// assert_eq!( tree.descendant_counts(), vec![ Some( 2 ), Some( 1 ), None, Some( 0 ) ] );
+    /// ```
+    pub fn descendant_counts( &self ) -> Vec<Option<usize>> {
+        let mut counts = self.nodes.iter().map( |node| node.as_ref().map( |_| 0 ) ).collect::<Vec<Option<usize>>>();
+        for top in self.parentless() {
+            let Ok( subtree ) = self.subtree( top ) else {
+                continue;
+            };
+
+            // Visiting the nodes in reverse pre-order ensures the children's counts are known before their parent's.
+            for index in subtree.into_iter().rev() {
+                if let Some( children ) = self.node( index ).unwrap().children.as_ref() {
+                    counts[ index ] = Some(
+                        children.iter().map( |&child| counts[ child ].unwrap_or( 0 ) + 1 ).sum()
+                    );
+                }
+            }
+        }
+        counts
+    }
+
+    /// Count the parent to child links (edges) of all the nodes, by summing the lengths of the nodes' children.
+    /// 
+    /// For a tree where all the nodes are connected to the root node, the number of edges is 1 less than the number of
+    /// nodes.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 297, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// assert_eq!( tree.count_edges(), 2, "Has 2 edges." );
+    /// ```
+    pub fn count_edges( &self ) -> usize {
+        self.nodes
+            .iter()
+            .flatten()
+            .map( |node| node.children.as_ref().map_or( 0, |children| children.len() ) )
+            .sum()
+    }
+
+    /// Count the nodes whose depth is within the inclusive range of `min` to `max`. If `min` is greater than `max`, then
+    /// 0 is returned.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 297, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 1, ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// assert_eq!( tree.count_between_depths( 1, 2 ), 3, "Has 3 nodes at depths 1 to 2." );
+    /// ```
+    pub fn count_between_depths( &self, min: usize, max: usize ) -> usize {
+        if min > max {
+            return 0;
+        }
+        let Some( root ) = self.root else {
+            return 0;
+        };
+        let mut count = 0;
+        let mut depth = 0;
+        let mut level = vec![ root ];
+        while !level.is_empty() && depth <= max {
+            if depth >= min {
+                count += level.len();
+            }
+            let mut next_level = Vec::<usize>::new();
+            for index in level {
+                if let Some( children ) = self.node( index ).and_then( |node| node.children.as_ref() ) {
+                    next_level.extend( children.iter() );
+                }
+            }
+            level = next_level;
+            depth += 1;
+        }
+        count
+    }
+
+    /// Count the nodes of the subtree `node_index` (including the node itself) that have the specified `feature`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 297, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 1, ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 1, ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// assert_eq!( tree.count_subtree_with_feature( 1, ALLOW_DATA ).unwrap(), 2, "Has 2 data nodes." );
+    /// ```
+    pub fn count_subtree_with_feature( &self, node_index: usize, feature: u8 ) -> Result<usize, TreeError> {
+        let mut count = 0;
+        for index in self.subtree( node_index )? {
+            if self.node( index ).unwrap().features & feature == feature {
+                count += 1;
+            }
+        }
+        Ok( count )
+    }
+
+    /// Count the nodes of the subtree `node_index`, being the node itself and all of its descendants.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 297, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 1, ALLOW_DATA, None, None ).ok();
+    /// assert_eq!( tree.subtree_count( 1 ).unwrap(), 2 );
+    /// assert_eq!( tree.subtree_count( 2 ).unwrap(), 1 );
+    /// ```
+    pub fn subtree_count( &self, node_index: usize ) -> Result<usize, TreeError> {
+        Ok( self.subtree( node_index )?.len() )
+    }
+
+    /// Count the descendants of the specified node `node_index` (excluding the node itself) for which the `predicate`
+    /// returns `true`, without collecting the matching nodes.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 297, ALLOW_CHILDREN | ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None ).ok();
+    /// let count = tree.count_descendants_matching( 0, |index, tree| tree.data_ref( index ).is_ok() ).unwrap();
+    /// assert_eq!( count, 1, "Only node 1 allows data." );
+    /// ```
+    pub fn count_descendants_matching<F>( &self, node_index: usize, predicate: F ) -> Result<usize, TreeError>
+    where
+        F: Fn( usize, &Tree ) -> bool
+    {
+        Ok(
+            self.subtree( node_index )?
+                .into_iter()
+                .skip( 1 )
+                .filter( |&index| predicate( index, self ) )
+                .count()
+        )
+    }
+
+    /// Compare the subtree `a` of the tree with the subtree `b` of the `other` tree (which may be the same tree), where
+    /// the subtrees are equal when they have the same structure and data.
+    /// 
+    /// The subtrees are walked together in pre-order, comparing the features and number of children of the
+    /// corresponding nodes, and calling `data_eq` with the data of the corresponding nodes that allow data. The
+    /// comparison stops at the first difference. The node types and data types are not compared.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 297, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 1, ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 3, ALLOW_DATA, None, None ).ok();
+    /// let same_len = |a: &Vec<Box<dyn core::any::Any>>, b: &Vec<Box<dyn core::any::Any>>| a.len() == b.len();
+    /// assert!( tree.subtree_eq_with( 1, &tree, 3, same_len ).unwrap() );
+    /// tree.data_mut( 4 ).unwrap().push( Box::new( 1i32 ) );
+    /// assert!( !tree.subtree_eq_with( 1, &tree, 3, same_len ).unwrap() );
+    /// ```
+    pub fn subtree_eq_with<F>( &self, a: usize, other: &Tree, b: usize, data_eq: F ) -> Result<bool, TreeError>
+    where
+        F: Fn( &Vec<Box<dyn Any>>, &Vec<Box<dyn Any>> ) -> bool
+    {
+        let mut stack = vec![ ( a, b ) ];
+        while let Some( ( a, b ) ) = stack.pop() {
+            let Some( node_a ) = self.node( a ) else {
+                return Err( TreeError::RetrievingNode( a ) )
+            };
+            let Some( node_b ) = other.node( b ) else {
+                return Err( TreeError::RetrievingNode( b ) )
+            };
+            if node_a.features != node_b.features {
+                return Ok( false );
+            }
+            if let ( Some( data_a ), Some( data_b ) ) = ( node_a.data.as_ref(), node_b.data.as_ref() ) {
+                if !data_eq( data_a, data_b ) {
+                    return Ok( false );
+                }
+            }
+            if let ( Some( children_a ), Some( children_b ) ) = ( node_a.children.as_ref(), node_b.children.as_ref() ) {
+                if children_a.len() != children_b.len() {
+                    return Ok( false );
+                }
+                stack.extend( children_a.iter().copied().zip( children_b.iter().copied() ).rev() );
+            }
+        }
+        Ok( true )
+    }
+
+    /// Count the nodes of the subtree `node_index` (including the node itself) for each combination of features, where
+    /// the key is the exact features value of the nodes.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 297, ALLOW_CHILDREN | ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// let histogram = tree.subtree_feature_histogram( 0 ).unwrap();
+    /// assert_eq!( histogram.get( &( ALLOW_CHILDREN | ALLOW_DATA ) ), Some( &1 ) );
+    /// assert_eq!( histogram.get( &ALLOW_DATA ), Some( &2 ) );
+    /// assert_eq!( histogram.get( &ALLOW_CHILDREN ), None );
+    /// ```
+    pub fn subtree_feature_histogram( &self, node_index: usize ) -> Result<BTreeMap<u8, usize>, TreeError> {
+        let mut histogram = BTreeMap::<u8, usize>::new();
+        for index in self.subtree( node_index )? {
+            *histogram.entry( self.node( index ).unwrap().features ).or_insert( 0 ) += 1;
+        }
+        Ok( histogram )
+    }
+
+    /// Obtain a read-only [`NodeView`] of the specified node `node_index`, for accessing all the fields of the node
+    /// with a single borrow of the tree.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 297, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, Some( Box::new( "Leaf".to_string() ) ), None ).ok();
+    /// tree.data_mut( 1 ).unwrap().push( Box::new( "String data".to_string() ) );
+    /// let view = tree.view( 1 ).unwrap();
+    /// assert_eq!( view.parent(), Some( 0 ) );
+    /// assert!( view.children().is_none() );
+    /// assert_eq!( view.data().unwrap().len(), 1 );
+    /// ```
+    /// 
+    /// [`NodeView`]: NodeView
+    pub fn view( &self, node_index: usize ) -> Result<NodeView<'_>, TreeError> {
+        let Some( node ) = self.node( node_index ) else {
+            return Err( TreeError::RetrievingNode( node_index ) )
+        };
+        Ok( NodeView { index: node_index, node } )
+    }
+
+    /// Obtain a read-only [`Cursor`] positioned at the specified node `node_index`, for navigating the tree from node
+    /// to node.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 297, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// let cursor = tree.cursor( 0 ).unwrap();
+    /// let first = cursor.first_child().unwrap();
+    /// assert_eq!( first.index(), 1 );
+    /// assert_eq!( first.next_sibling().unwrap().index(), 2 );
+    /// assert_eq!( first.parent().unwrap().index(), 0 );
+    /// ```
+    /// 
+    /// [`Cursor`]: Cursor
+    pub fn cursor( &self, node_index: usize ) -> Result<Cursor<'_>, TreeError> {
+        if self.node( node_index ).is_none() {
+            return Err( TreeError::RetrievingNode( node_index ) );
+        }
+        Ok( Cursor { tree: self, index: node_index } )
+    }
+
+    /// Count the nodes of the tree for each node type of the type `T`. Nodes without a node type, or with a node type
+    /// of a different type, are not counted.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 297, ALLOW_CHILDREN, Some( Box::new( "Root".to_string() ) ), None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, Some( Box::new( "Leaf".to_string() ) ), None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, Some( Box::new( "Leaf".to_string() ) ), None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// let counts = tree.count_by_node_type::<String>();
+    /// assert_eq!( counts.get( "Leaf" ), Some( &2 ) );
+    /// assert_eq!( counts.get( "Root" ), Some( &1 ) );
+    /// ```
+    pub fn count_by_node_type<T: Any + Eq + core::hash::Hash + Clone>( &self ) -> HashMap<T, usize> {
+        let mut counts = HashMap::<T, usize>::new();
+        for node in self.nodes.iter().flatten() {
+            if let Some( node_type ) = node.node_type.as_ref().and_then( |node_type| node_type.downcast_ref::<T>() ) {
+                *counts.entry( node_type.clone() ).or_insert( 0 ) += 1;
+            }
+        }
+        counts
+    }
+
+    /// Obtain the [`NodeSummary`] of the specified node `node_index`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 297, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// tree.data_mut( 1 ).unwrap().push( Box::new( "String data".to_string() ) );
+    /// let summary = tree.node_summary( 1 ).unwrap();
+    /// assert_eq!( summary.depth, 1, "Depth of node 1 is 1." );
+    /// assert_eq!( summary.parent, Some( 0 ), "Parent of node 1 is root." );
+    /// assert_eq!( summary.data_len, 1, "Node 1 has 1 data item." );
+    /// ```
+    /// 
+    /// [`NodeSummary`]: NodeSummary
+    pub fn node_summary( &self, node_index: usize ) -> Result<NodeSummary, TreeError> {
+        let Some( index_node ) = self.node( node_index ) else {
+            return Err( TreeError::RetrievingNode( node_index ) )
+        };
+        Ok( NodeSummary {
+            index: node_index,
+            depth: self.depth( node_index )?,
+            parent: index_node.parent,
+            features: index_node.features,
+            data_len: index_node.data.as_ref().map_or( 0, |data| data.len() ),
+        } )
+    }
+
+    /// Flatten the tree into a vector of [`NodeSummary`], by walking the tree in pre-order from the root node.
+    /// 
+    /// The summaries contain no [`Any`] data, thus are suited for logging and assertions. An empty tree yields an empty
+    /// vector.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 297, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// tree.data_mut( 1 ).unwrap().push( Box::new( "String data".to_string() ) );
+    /// let summaries = tree.flatten();
+    /// assert_eq!( summaries.len(), 2, "Has 2 summaries." );
+    /// assert_eq!( summaries[ 1 ].depth, 1, "Depth of node 1 is 1." );
+    /// assert_eq!( summaries[ 1 ].parent, Some( 0 ), "Parent of node 1 is root." );
+    /// assert_eq!( summaries[ 1 ].data_len, 1, "Node 1 has 1 data item." );
+    /// ```
+    /// 
+    /// [`NodeSummary`]: NodeSummary
+    /// [`Any`]: core::any::Any
+    pub fn flatten( &self ) -> Vec<NodeSummary> {
+        let mut summaries = Vec::<NodeSummary>::new();
+        let Some( root ) = self.root else {
+            return summaries;
+        };
+        let mut stack = vec![ ( root, 0 ) ];
+        while let Some( ( index, depth ) ) = stack.pop() {
+            let Some( node ) = self.node( index ) else {
+                continue;
+            };
+            summaries.push( NodeSummary {
+                index,
+                depth,
+                parent: node.parent,
+                features: node.features,
+                data_len: node.data.as_ref().map_or( 0, |data| data.len() ),
+            } );
+            if let Some( children ) = node.children.as_ref() {
+                stack.extend( children.iter().rev().map( |&child| ( child, depth + 1 ) ) );
+            }
+        }
+        summaries
+    }
+
+    /// Obtain the structure of the tree as a nested [`TreeShape`] value, starting from the root node, where each node
+    /// has its features and the shapes of its children. An empty tree yields `None`.
+    /// 
+    /// The shape contains no node types, data types or data, thus is easily compared in assertions.
+    /// 
+    /// # WARNING
+    /// 
+    /// The shape is built and dropped without recursion, though the derived [`Clone`], [`PartialEq`] and [`Debug`]
+    /// implementations of [`TreeShape`] recurse once per level of depth, thus may overflow the stack for very deep
+    /// trees.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA, TreeShape};
+    /// 
+    /// let mut tree = Tree::new();
+    /// assert_eq!( tree.to_shape(), None );
+    /// tree.insert( 297, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// assert_eq!(
+    ///     tree.to_shape(),
+    ///     Some( TreeShape::Node {
+    ///         features: ALLOW_CHILDREN,
+    ///         children: vec![ TreeShape::Node { features: ALLOW_DATA, children: Vec::new() } ],
+    ///     } )
+    /// );
+    /// ```
+    /// 
+    /// [`TreeShape`]: TreeShape
+    pub fn to_shape( &self ) -> Option<TreeShape> {
+        Some( self.shape( self.root? ) )
+    }
+
+    /// Obtain the edges of the subtree `node_index` in pre-order, where each edge is a tuple of a node index and the
+    /// index of its parent within the subtree. The node `node_index` is the subtree's root node, thus has the parent
+    /// `None`.
+    /// 
+    /// The edges can be used to recreate the subtree's structure with [`insert_subtree_from_edges`].
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 297, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 1, ALLOW_DATA, None, None ).ok();
+    /// assert_eq!( tree.subtree_to_parent_edges( 1 ).unwrap(), vec![ ( 1, None ), ( 2, Some( 1 ) ) ] );
+    /// ```
+    /// 
+    /// [`insert_subtree_from_edges`]: Tree::insert_subtree_from_edges
+    pub fn subtree_to_parent_edges( &self, node_index: usize ) -> Result<Vec<( usize, Option<usize> )>, TreeError> {
+        Ok(
+            self.subtree( node_index )?
+                .into_iter()
+                .map( |index| {
+                    if index == node_index {
+                        ( index, None )
+                    } else {
+                        ( index, self.node( index ).unwrap().parent )
+                    }
+                } )
+                .collect()
+        )
+    }
+
+    /// Obtain the indices of the subtree of the specified node `start` in pre-order (depth first), starting with
+    /// `start`.
+    /// 
+    /// The indices are a snapshot at the time of the call, thus the tree can be mutated while looping over the indices.
+    /// If the tree structure is changed during the loop, some of the indices may become stale (such as deleted nodes).
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 297, ALLOW_CHILDREN | ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 1, ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// for index in tree.dfs_order( 0 ).unwrap() {
+    ///     if let Ok( data ) = tree.data_mut( index ) {
+    ///         data.push( Box::new( index ) );
+    ///     }
+    /// }
+    /// assert_eq!( tree.dfs_order( 0 ).unwrap(), vec![ 0, 1, 2, 3 ] );
+    /// assert_eq!( tree.data_ref( 3 ).unwrap().len(), 1 );
+    /// ```
+    pub fn dfs_order( &self, start: usize ) -> Result<Vec<usize>, TreeError> {
+        self.subtree( start )
+    }
+
+    /// Iterate over the nodes of every component of the tree in pre-order (depth first), where a component is a node
+    /// without a parent and its descendants. The components are visited in ascending order of the indices of their
+    /// nodes without a parent.
+    /// 
+    /// For a tree where all the nodes are connected to the root node, this is the same as [`dfs_order`] of the root
+    /// node.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 297, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 1, ALLOW_DATA, None, None ).ok();
+    /// assert_eq!( tree.iter_forest().collect::<Vec<usize>>(), vec![ 0, 1, 3, 2 ] );
+    /// ```
+    /// 
+    /// [`dfs_order`]: Tree::dfs_order
+    pub fn iter_forest( &self ) -> impl Iterator<Item = usize> + '_ {
+        self.parentless().flat_map( move |index| self.subtree( index ).unwrap_or_default() )
+    }
+
+    /// Obtain each component of the tree with the number of its nodes, where a component is a node without a parent
+    /// and its descendants. Each entry is a tuple of the index of the node without a parent and the number of nodes of
+    /// the component, in ascending order of the indices.
+    /// 
+    /// For a tree where all the nodes are connected to the root node, there is only the entry of the root node.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 297, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 1, ALLOW_DATA, None, None ).ok();
+    /// assert_eq!( tree.forest_summary(), vec![ ( 0, 3 ) ] );
+    /// ```
+    pub fn forest_summary( &self ) -> Vec<( usize, usize )> {
+        self.parentless()
+            .map( |index| ( index, self.subtree( index ).map_or( 0, |subtree| subtree.len() ) ) )
+            .collect()
+    }
+
+    /// Obtain at most `limit` indices of the subtree of the specified node `node_index` in pre-order (depth first),
+    /// starting with `node_index`.
+    /// 
+    /// The traversal stops once `limit` indices are obtained, thus large subtrees are not entirely visited.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 297, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 1, ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// assert_eq!( tree.preview( 0, 3 ).unwrap(), vec![ 0, 1, 2 ] );
+    /// assert_eq!( tree.preview( 1, 3 ).unwrap(), vec![ 1, 2 ] );
+    /// ```
+    pub fn preview( &self, node_index: usize, limit: usize ) -> Result<Vec<usize>, TreeError> {
+        self.subtree_limited( node_index, limit )
+    }
+
+    /// Iterate over the nodes of the tree in pre-order from the root node, where each item is a tuple of the node
+    /// index and the path from the root node down to the node (inclusive of both).
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 297, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 1, ALLOW_DATA, None, None ).ok();
+    /// let items = tree.iter_with_path().collect::<Vec<( usize, Vec<usize> )>>();
+    /// assert_eq!( items[ 0 ], ( 0, vec![ 0 ] ) );
+    /// assert_eq!( items[ 2 ], ( 2, vec![ 0, 1, 2 ] ) );
+    /// ```
+    pub fn iter_with_path( &self ) -> impl Iterator<Item = ( usize, Vec<usize> )> + '_ {
+        let mut stack = Vec::<( usize, Vec<usize> )>::new();
+        if let Some( root ) = self.root {
+            stack.push( ( root, vec![ root ] ) );
+        }
+        core::iter::from_fn( move || {
+            let ( index, path ) = stack.pop()?;
+            if let Some( children ) = self.node( index ).and_then( |node| node.children.as_ref() ) {
+                for child in children.iter().rev() {
+                    let mut child_path = path.clone();
+                    child_path.push( *child );
+                    stack.push( ( *child, child_path ) );
+                }
+            }
+            Some( ( index, path ) )
+        } )
+    }
+
+    /// Call `f` once for each depth level of the tree, starting at the root node's level 0, with the depth and the
+    /// indices of the nodes at that depth. The nodes of each level are in breadth first order.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 297, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// let mut levels = Vec::<Vec<usize>>::new();
+    /// tree.each_level( |_, nodes| levels.push( nodes.to_vec() ) );
+    /// assert_eq!( levels, vec![ vec![ 0 ], vec![ 1, 2 ] ] );
+    /// ```
+    pub fn each_level<F>( &self, mut f: F )
+    where
+        F: FnMut( usize, &[usize] )
+    {
+        let Some( root ) = self.root else {
+            return;
+        };
+        let mut depth = 0;
+        let mut level = vec![ root ];
+        while !level.is_empty() {
+            f( depth, &level );
+            let mut next_level = Vec::<usize>::new();
+            for index in level {
+                if let Some( children ) = self.node( index ).and_then( |node| node.children.as_ref() ) {
+                    next_level.extend( children.iter() );
+                }
+            }
+            level = next_level;
+            depth += 1;
+        }
+    }
+
+    // -- Data methods --
+
+    /// Obtain a mutable reference to the node's data for the specified node `node_index`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 974, ALLOW_CHILDREN | ALLOW_DATA, None, None ).ok();
+    /// tree.data_mut( 0 ).unwrap().push( Box::new( "String data".to_string() ) );
+    /// let data_vec_mut = tree.data_mut( 0 ).ok().unwrap();
+    /// let data = data_vec_mut.get_mut( 0 ).unwrap().downcast_mut::<String>().unwrap();
+    /// 
+    /// // mutate the data
+    /// *data = "Mutated data".to_string();
+    /// 
+    /// // Take node to check if data did mutate.
+    /// let mut data_vec = tree.take( 0 ).ok().unwrap().unwrap(); // Deleting root node, and take data.
+    /// let data_taken = data_vec.pop().unwrap().downcast::<String>().ok().unwrap();
+    /// assert_eq!( tree.count(), 0, "0 nodes are present." );
+    /// assert_eq!( *data_taken, "Mutated data".to_string(), "Data of node is a mutated string" );
+    /// ```
+    pub fn data_mut( &mut self, node_index: usize ) -> Result<&mut Vec<Box<dyn Any>>, TreeError> {
+        let Some( index_node ) = self.node( node_index ) else {
+            return Err( TreeError::RetrievingNode( node_index ) );
+        };
+        if index_node.features & ALLOW_DATA != ALLOW_DATA/* !index_node.features.allow_data*/ {
+            return Err( TreeError::NoDataAllowed( node_index ) );
+        }
+        self.notify( Mutation::Data( node_index ) );
+        Ok( self.node_mut( node_index ).unwrap().data.as_mut().unwrap() )
+    }
+
+    /// Obtain an immutable reference to the node's data for the specified node `node_index`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 550, ALLOW_CHILDREN | ALLOW_DATA, None, None ).ok();
+    /// tree.data_mut( 0 ).unwrap().push( Box::new( "String data".to_string() ) );
+    /// let data_vec_ref = tree.data_ref( 0 ).ok().unwrap();
+    /// let data = data_vec_ref.get( 0 ).unwrap().downcast_ref::<String>().unwrap();
+    /// assert_eq!( *data, "String data".to_string() );
+    /// ```
+    pub fn data_ref( &self, node_index: usize ) -> Result<&Vec<Box<dyn Any>>, TreeError> {
+        let Some( index_node ) = self.node( node_index ) else {
+            return Err( TreeError::RetrievingNode( node_index ) )
+        };
+        if index_node.features & ALLOW_DATA != ALLOW_DATA/* !index_node.features.allow_data*/ {
+            return Err( TreeError::NoDataAllowed( node_index ) );
+        }
+        Ok( index_node.data.as_ref().unwrap() )
+    }
+
+    /// Obtain an immutable reference to the data item at `position` of the specified node `node_index`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 550, ALLOW_CHILDREN | ALLOW_DATA, None, None ).ok();
+    /// tree.data_mut( 0 ).unwrap().push( Box::new( "String data".to_string() ) );
+    /// let data = tree.data_get_ref( 0, 0 ).unwrap().downcast_ref::<String>().unwrap();
+    /// assert_eq!( *data, "String data".to_string() );
+    /// assert!( tree.data_get_ref( 0, 1 ).is_err(), "No data at position 1." );
+    /// ```
+    #[allow( clippy::borrowed_box )] // The data items are stored as `Box<dyn Any>`, as provided by `data_ref`.
+    pub fn data_get_ref( &self, node_index: usize, position: usize ) -> Result<&Box<dyn Any>, TreeError> {
+        let Some( data ) = self.data_ref( node_index )?.get( position ) else {
+            return Err( TreeError::NoDataFound( position, node_index ) )
+        };
+        Ok( data )
+    }
+
+    /// Obtain a mutable reference to the data item at `position` of the specified node `node_index`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 550, ALLOW_CHILDREN | ALLOW_DATA, None, None ).ok();
+    /// tree.data_mut( 0 ).unwrap().push( Box::new( 1i32 ) );
+    /// *tree.data_get_mut( 0, 0 ).unwrap().downcast_mut::<i32>().unwrap() += 1;
+    /// assert_eq!( *tree.data_get_ref( 0, 0 ).unwrap().downcast_ref::<i32>().unwrap(), 2 );
+    /// ```
+    pub fn data_get_mut( &mut self, node_index: usize, position: usize ) -> Result<&mut Box<dyn Any>, TreeError> {
+        let Some( data ) = self.data_mut( node_index )?.get_mut( position ) else {
+            return Err( TreeError::NoDataFound( position, node_index ) )
+        };
+        Ok( data )
+    }
+
+    /// Remove the data item at `position` of the specified node `node_index`, and return the data item.
+    /// 
+    /// The last data item is moved into `position`, thus the order of the data is not preserved. This avoids shifting
+    /// all the data items after `position`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 550, ALLOW_CHILDREN | ALLOW_DATA, None, None ).ok();
+    /// tree.data_mut( 0 ).unwrap().push( Box::new( 1i32 ) );
+    /// tree.data_mut( 0 ).unwrap().push( Box::new( 2i32 ) );
+    /// tree.data_mut( 0 ).unwrap().push( Box::new( 3i32 ) );
+    /// let removed = tree.data_swap_remove( 0, 0 ).unwrap();
+    /// assert_eq!( *removed.downcast_ref::<i32>().unwrap(), 1 );
+    /// assert_eq!( *tree.data_ref( 0 ).unwrap()[ 0 ].downcast_ref::<i32>().unwrap(), 3 );
+    /// ```
+    pub fn data_swap_remove( &mut self, node_index: usize, position: usize ) -> Result<Box<dyn Any>, TreeError> {
+        let data = self.data_mut( node_index )?;
+        if position >= data.len() {
+            return Err( TreeError::NoDataFound( position, node_index ) );
+        }
+        Ok( data.swap_remove( position ) )
+    }
+
+    /// Retain only the data items of the specified node `node_index` for which the `predicate` returns `true`, keeping
+    /// their order, and return the number of data items removed.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 550, ALLOW_CHILDREN | ALLOW_DATA, None, None ).ok();
+    /// tree.data_mut( 0 ).unwrap().push( Box::new( 1i32 ) );
+    /// tree.data_mut( 0 ).unwrap().push( Box::new( 2i32 ) );
+    /// let removed = tree.data_retain( 0, |data| data.downcast_ref::<i32>() == Some( &2 ) ).unwrap();
+    /// assert_eq!( removed, 1 );
+    /// assert_eq!( tree.data_ref( 0 ).unwrap().len(), 1 );
+    /// ```
+    pub fn data_retain<F>( &mut self, node_index: usize, mut predicate: F ) -> Result<usize, TreeError>
+    where
+        F: FnMut( &Box<dyn Any> ) -> bool
+    {
+        let data = self.data_mut( node_index )?;
+        let length = data.len();
+        data.retain( |item| predicate( item ) );
+        Ok( length - data.len() )
+    }
+
+    /// Obtain the positions of the data items of the specified node `node_index` that are of the type `T`.
+    /// 
+    /// Useful for a node containing data of different types, to process only the data items of a certain type.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 550, ALLOW_CHILDREN | ALLOW_DATA, None, None ).ok();
+    /// tree.data_mut( 0 ).unwrap().push( Box::new( "String data".to_string() ) );
+    /// tree.data_mut( 0 ).unwrap().push( Box::new( 5i32 ) );
+    /// assert_eq!( tree.data_positions_of_type::<i32>( 0 ).unwrap(), vec![ 1 ] );
+    /// ```
+    pub fn data_positions_of_type<T: Any>( &self, node_index: usize ) -> Result<Vec<usize>, TreeError> {
+        Ok(
+            self.data_ref( node_index )?
+                .iter()
+                .enumerate()
+                .filter( |( _, data )| data.is::<T>() )
+                .map( |( position, _ )| position )
+                .collect()
+        )
+    }
+
+    /// Iterate over the data items of the specified node `node_index` downcast to the type `T`, yielding `Some` for the
+    /// data items of the type `T`, and `None` for the data items of a different type.
+    /// 
+    /// Useful for a node containing data of a single type, to avoid downcasting each data item.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 550, ALLOW_CHILDREN | ALLOW_DATA, None, None ).ok();
+    /// tree.data_mut( 0 ).unwrap().push( Box::new( 1i32 ) );
+    /// tree.data_mut( 0 ).unwrap().push( Box::new( "String data".to_string() ) );
+    /// let items = tree.iter_data_typed::<i32>( 0 ).unwrap().collect::<Vec<Option<&i32>>>();
+    /// assert_eq!( items, vec![ Some( &1 ), None ] );
+    /// ```
+    pub fn iter_data_typed<T: Any>(
+        &self,
+        node_index: usize
+    ) -> Result<impl Iterator<Item = Option<&T>> + '_, TreeError> {
+        Ok( self.data_ref( node_index )?.iter().map( |data| data.downcast_ref::<T>() ) )
+    }
+
+    /// Obtain the position of the first data item of the specified node `node_index` for which the `predicate` returns
+    /// `true`, else `None` when no data item matches.
+    /// 
+    /// Combined with [`data_swap_remove`] or [`data_mut`], a data item can be found and then removed.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 550, ALLOW_CHILDREN | ALLOW_DATA, None, None ).ok();
+    /// tree.data_mut( 0 ).unwrap().push( Box::new( 1i32 ) );
+    /// tree.data_mut( 0 ).unwrap().push( Box::new( 2i32 ) );
+    /// let position = tree.position_of_data( 0, |data| data.downcast_ref::<i32>() == Some( &2 ) ).unwrap();
+    /// assert_eq!( position, Some( 1 ) );
+    /// assert_eq!( tree.position_of_data( 0, |data| data.is::<String>() ).unwrap(), None );
+    /// ```
+    /// 
+    /// [`data_swap_remove`]: Tree::data_swap_remove
+    /// [`data_mut`]: Tree::data_mut
+    pub fn position_of_data<F>( &self, node_index: usize, predicate: F ) -> Result<Option<usize>, TreeError>
+    where
+        F: Fn( &Box<dyn Any> ) -> bool
+    {
+        Ok( self.data_ref( node_index )?.iter().position( predicate ) )
+    }
+
+    /// Duplicate the data item at `position` of the specified node `node_index`, and append the copy to the end of
+    /// the node's data. As [`Any`] data can't be cloned directly, the copy is made by calling `clone_fn` with the
+    /// data item.
+    /// 
+    /// If no error, the returned [`usize`] value is the position of the copy in the node's data.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// use core::any::Any;
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 550, ALLOW_CHILDREN | ALLOW_DATA, None, None ).ok();
+    /// tree.data_mut( 0 ).unwrap().push( Box::new( "String data".to_string() ) );
+    /// let position = tree.duplicate_data( 0, 0, |data| {
+    ///     Box::new( data.downcast_ref::<String>().unwrap().clone() ) as Box<dyn Any>
+    /// } ).unwrap();
+    /// assert_eq!( position, 1, "Copy is at position 1." );
+    /// ```
+    /// 
+    /// [`Any`]: core::any::Any
+    /// [`usize`]: usize
+    pub fn duplicate_data<F>( &mut self, node_index: usize, position: usize, clone_fn: F ) -> Result<usize, TreeError>
+    where
+        F: Fn( &Box<dyn Any> ) -> Box<dyn Any>
+    {
+        let data = self.data_mut( node_index )?;
+        let Some( item ) = data.get( position ) else {
+            return Err( TreeError::NoDataFound( position, node_index ) )
+        };
+        let copy = clone_fn( item );
+        data.push( copy );
+        Ok( data.len() - 1 )
+    }
+
+    /// Move all the data of the node `from` to the end of the data of the node `into`, leaving the node `from` without
+    /// data. Both nodes must allow data. Nothing is done when `into` and `from` are the same node.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 550, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// tree.data_mut( 1 ).unwrap().push( Box::new( "x".to_string() ) );
+    /// tree.data_mut( 2 ).unwrap().push( Box::new( "y".to_string() ) );
+    /// tree.merge_data_from( 1, 2 ).ok();
+    /// assert_eq!( tree.data_ref( 1 ).unwrap().len(), 2, "Node 1 has 2 data items." );
+    /// assert!( tree.data_ref( 2 ).unwrap().is_empty(), "Node 2 has no data." );
+    /// ```
+    pub fn merge_data_from( &mut self, into: usize, from: usize ) -> Result<(), TreeError> {
+        self.data_ref( into )?;
+        self.data_ref( from )?;
+        if into == from {
+            return Ok( () );
+        }
+        let mut data = core::mem::take( self.data_mut( from )? );
+        self.data_mut( into )?.append( &mut data );
+        Ok( () )
+    }
+
+    /// Remove all the data of the nodes in the subtree `node_index`, including the node itself, while keeping the
+    /// structure of the subtree. Returns the number of data items removed.
+    /// 
+    /// # WARNING
+    /// 
+    /// All data in the subtree will be destroyed.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 550, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// tree.data_mut( 1 ).unwrap().push( Box::new( "String data".to_string() ) );
+    /// assert_eq!( tree.clear_subtree_data( 0 ).unwrap(), 1, "Removed 1 data item." );
+    /// assert!( tree.data_ref( 1 ).unwrap().is_empty(), "Node 1 has no data." );
+    /// ```
+    pub fn clear_subtree_data( &mut self, node_index: usize ) -> Result<usize, TreeError> {
+        let mut removed = 0;
+        for index in self.subtree( node_index )? {
+            if let Some( data ) = self.node_mut( index ).unwrap().data.as_mut() {
+                if !data.is_empty() {
+                    removed += data.len();
+                    data.clear();
+                    self.notify( Mutation::Data( index ) );
+                }
+            }
+        }
+        Ok( removed )
+    }
+
+    /// Remove all the data of every node of the tree, while keeping the structure, features, node types and data types
+    /// of the nodes. Returns the number of data items removed.
+    /// 
+    /// Unlike [`clear`], which removes all the nodes, the tree can be reused as a template.
+    /// 
+    /// # WARNING
+    /// 
+    /// All data in the tree will be destroyed.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 550, ALLOW_CHILDREN | ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// tree.data_mut( 0 ).unwrap().push( Box::new( "String data".to_string() ) );
+    /// tree.data_mut( 1 ).unwrap().push( Box::new( "String data".to_string() ) );
+    /// assert_eq!( tree.clear_all_data(), 2, "Removed 2 data items." );
+    /// assert_eq!( tree.count(), 2, "2 nodes are present." );
+    /// ```
+    /// 
+    /// [`clear`]: Tree::clear
+    pub fn clear_all_data( &mut self ) -> usize {
+        let mut removed = 0;
+        let mut changed = Vec::<usize>::new();
+        for ( index, node ) in self.nodes.iter_mut().enumerate() {
+            if let Some( data ) = node.as_mut().and_then( |node| node.data.as_mut() ) {
+                if !data.is_empty() {
+                    removed += data.len();
+                    data.clear();
+                    changed.push( index );
+                }
+            }
+        }
+        for index in changed {
+            self.notify( Mutation::Data( index ) );
+        }
+        removed
+    }
+
+    /// Call `f` with the index and a mutable reference to the data of every node allowing data, in ascending order of
+    /// the node indices.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 550, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// tree.each_data_mut( |index, data| data.push( Box::new( index ) ) );
+    /// assert_eq!( *tree.data_ref( 2 ).unwrap()[ 0 ].downcast_ref::<usize>().unwrap(), 2 );
+    /// ```
+    pub fn each_data_mut<F>( &mut self, mut f: F )
+    where
+        F: FnMut( usize, &mut Vec<Box<dyn Any>> )
+    {
+        let mut accessed = Vec::<usize>::new();
+        for ( index, node ) in self.nodes.iter_mut().enumerate() {
+            if let Some( data ) = node.as_mut().and_then( |node| node.data.as_mut() ) {
+                accessed.push( index );
+                f( index, data );
+            }
+        }
+        for index in accessed {
+            self.notify( Mutation::Data( index ) );
+        }
+    }
+
+    /// Take all the data items of the type `T` out of every node of the tree, in ascending order of the node indices.
+    /// Data items of other types are left in place.
+    /// 
+    /// Each entry is a tuple of the index of the node the data item was taken from, and the data item.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 550, ALLOW_CHILDREN | ALLOW_DATA, None, None ).ok();
+    /// tree.data_mut( 0 ).unwrap().push( Box::new( "String data".to_string() ) );
+    /// tree.data_mut( 0 ).unwrap().push( Box::new( 42i32 ) );
+    /// let taken = tree.take_all_of_type::<i32>();
+    /// assert_eq!( taken, vec![ ( 0, 42 ) ] );
+    /// assert_eq!( tree.data_ref( 0 ).unwrap().len(), 1, "String data is left." );
+    /// ```
+    pub fn take_all_of_type<T: Any>( &mut self ) -> Vec<( usize, T )> {
+        let mut taken = Vec::<( usize, T )>::new();
+        for ( index, node ) in self.nodes.iter_mut().enumerate() {
+            let Some( data ) = node.as_mut().and_then( |node| node.data.as_mut() ) else {
+                continue;
+            };
+            let mut kept = Vec::<Box<dyn Any>>::with_capacity( data.len() );
+            for item in data.drain( .. ) {
+                match item.downcast::<T>() {
+                    Ok( value ) => taken.push( ( index, *value ) ),
+                    Err( item ) => kept.push( item ),
+                }
+            }
+            *data = kept;
+        }
+        let mut changed = taken.iter().map( |( index, _ )| *index ).collect::<Vec<usize>>();
+        changed.dedup();
+        for index in changed {
+            self.notify( Mutation::Data( index ) );
+        }
+        taken
+    }
+
+    /// Obtain immutable references to the data of all the nodes having data in the subtree `node_index`, including the
+    /// node itself. The nodes are visited in pre-order, and the nodes without the [`ALLOW_DATA`] feature are skipped.
+    /// 
+    /// Each entry is a tuple of the node index and a reference to the node's data.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 550, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// tree.data_mut( 2 ).unwrap().push( Box::new( "String data".to_string() ) );
+    /// let data_refs = tree.subtree_data_refs( 0 ).unwrap();
+    /// assert_eq!( data_refs.len(), 2, "Has 2 data nodes." );
+    /// assert_eq!( data_refs[ 1 ].0, 2, "Second entry is node 2." );
+    /// assert_eq!( data_refs[ 1 ].1.len(), 1, "Node 2 has 1 data item." );
+    /// ```
+    /// 
+    /// [`ALLOW_DATA`]: ALLOW_DATA
+    #[allow( clippy::type_complexity )]
+    pub fn subtree_data_refs( &self, node_index: usize ) -> Result<Vec<( usize, &Vec<Box<dyn Any>> )>, TreeError> {
+        let mut data_refs = Vec::<( usize, &Vec<Box<dyn Any>> )>::new();
+        for index in self.subtree( node_index )? {
+            if let Some( data ) = self.node( index ).unwrap().data.as_ref() {
+                data_refs.push( ( index, data ) );
+            }
+        }
+        Ok( data_refs )
+    }
+
+    /// Obtain immutable references to the data of the children of the specified node `node_index`, in the order of the
+    /// children.
+    /// 
+    /// Each entry is a tuple of the child's index and a reference to the child's data, or `None` when the child doesn't
+    /// allow data.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 550, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// let data_refs = tree.children_data_refs( 0 ).unwrap();
+    /// assert!( data_refs[ 0 ].1.is_none(), "Node 1 doesn't allow data." );
+    /// assert!( data_refs[ 1 ].1.is_some(), "Node 2 allows data." );
+    /// ```
+    #[allow( clippy::type_complexity )]
+    pub fn children_data_refs(
+        &self,
+        node_index: usize
+    ) -> Result<Vec<( usize, Option<&Vec<Box<dyn Any>>> )>, TreeError> {
+        let mut data_refs = Vec::<( usize, Option<&Vec<Box<dyn Any>>> )>::new();
+        for child in self.children( node_index )? {
+            let Some( node ) = self.node( *child ) else {
+                return Err( TreeError::RetrievingNode( *child ) )
+            };
+            data_refs.push( ( *child, node.data.as_ref() ) );
+        }
+        Ok( data_refs )
     }
 
-    /// Count the nodes of the tree.
+    /// Obtain immutable references to the data of the leaf nodes (nodes without children) of the subtree `start`, in
+    /// pre-order (left to right). Leaf nodes that don't allow data are skipped.
+    /// 
+    /// Each entry is a tuple of the leaf node's index and a reference to the leaf node's data.
     /// 
     /// # Examples
     /// 
@@ -742,17 +4208,28 @@ impl Tree {
     /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
     /// 
     /// let mut tree = Tree::new();
-    /// tree.insert( 297, ALLOW_CHILDREN | ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 550, ALLOW_CHILDREN | ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 0, ALLOW_CHILDREN, None, None ).ok();
     /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
-    /// assert_eq!( tree.count(), 2, "Has 2 nodes." );
+    /// tree.data_mut( 2 ).unwrap().push( Box::new( "String data".to_string() ) );
+    /// let data_refs = tree.leaf_data_refs( 0 ).unwrap();
+    /// assert_eq!( data_refs.len(), 1, "Node 1 doesn't allow data, and node 0 isn't a leaf." );
+    /// assert_eq!( data_refs[ 0 ].0, 2 );
     /// ```
-    pub fn count( &self ) -> usize {
-        self.nodes.iter().filter( |n| !n.is_none() ).count()
+    #[allow( clippy::type_complexity )]
+    pub fn leaf_data_refs( &self, start: usize ) -> Result<Vec<( usize, &Vec<Box<dyn Any>> )>, TreeError> {
+        Ok(
+            self.subtree_leaves( start )?
+                .into_iter()
+                .filter_map( |index| self.node( index ).unwrap().data.as_ref().map( |data| ( index, data ) ) )
+                .collect()
+        )
     }
 
-    // -- Data methods --
-
-    /// Obtain a mutable reference to the node's data for the specified node `node_index`.
+    /// Obtain immutable references to the data of all the nodes having data in the tree, in ascending order of the node
+    /// indices. Unlike [`subtree_data_refs`], every node is included regardless of being connected to the root node.
+    /// 
+    /// Each entry is a tuple of the node index and a reference to the node's data.
     /// 
     /// # Examples
     /// 
@@ -760,31 +4237,28 @@ impl Tree {
     /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
     /// 
     /// let mut tree = Tree::new();
-    /// tree.insert( 974, ALLOW_CHILDREN | ALLOW_DATA, None, None ).ok();
-    /// tree.data_mut( 0 ).unwrap().push( Box::new( "String data".to_string() ) );
-    /// let data_vec_mut = tree.data_mut( 0 ).ok().unwrap();
-    /// let data = data_vec_mut.get_mut( 0 ).unwrap().downcast_mut::<String>().unwrap();
-    /// 
-    /// // mutate the data
-    /// *data = "Mutated data".to_string();
-    /// 
-    /// // Take node to check if data did mutate.
-    /// let mut data_vec = tree.take( 0 ).ok().unwrap().unwrap(); // Deleting root node, and take data.
-    /// let data_taken = data_vec.pop().unwrap().downcast::<String>().ok().unwrap();
-    /// assert_eq!( tree.count(), 0, "0 nodes are present." );
-    /// assert_eq!( *data_taken, "Mutated data".to_string(), "Data of node is a mutated string" );
+    /// tree.insert( 550, ALLOW_CHILDREN, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    /// let data_refs = tree.data_ref_all();
+    /// assert_eq!( data_refs.len(), 2, "Has 2 data nodes." );
+    /// assert_eq!( data_refs[ 0 ].0, 1, "First entry is node 1." );
     /// ```
-    pub fn data_mut( &mut self, node_index: usize ) -> Result<&mut Vec<Box<dyn Any>>, TreeError> {
-        let Some( index_node ) = self.node_mut( node_index ) else {
-            return Err( TreeError::RetrievingNode( node_index ) );
-        };
-        if index_node.features & ALLOW_DATA != ALLOW_DATA/* !index_node.features.allow_data*/ {
-            return Err( TreeError::NoDataAllowed( node_index ) );
-        }
-        Ok( index_node.data.as_mut().unwrap() )
+    /// 
+    /// [`subtree_data_refs`]: Tree::subtree_data_refs
+    pub fn data_ref_all( &self ) -> Vec<( usize, &Vec<Box<dyn Any>> )> {
+        self.nodes
+            .iter()
+            .enumerate()
+            .filter_map( |( index, node )| {
+                node.as_ref()
+                    .and_then( |node| node.data.as_ref() )
+                    .map( |data| ( index, data ) )
+            } )
+            .collect()
     }
 
-    /// Obtain an immutable reference to the node's data for the specified node `node_index`.
+    /// Obtain reference to the data type for the specified node `node_index`.
     /// 
     /// # Examples
     /// 
@@ -792,23 +4266,21 @@ impl Tree {
     /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
     /// 
     /// let mut tree = Tree::new();
-    /// tree.insert( 550, ALLOW_CHILDREN | ALLOW_DATA, None, None ).ok();
-    /// tree.data_mut( 0 ).unwrap().push( Box::new( "String data".to_string() ) );
-    /// let data_vec_ref = tree.data_ref( 0 ).ok().unwrap();
-    /// let data = data_vec_ref.get( 0 ).unwrap().downcast_ref::<String>().unwrap();
-    /// assert_eq!( *data, "String data".to_string() );
+    /// tree.insert( 514, ALLOW_CHILDREN | ALLOW_DATA, None, Some( Box::new( "String".to_string() ) ) ).ok();
+    /// let type_any_ref = tree.data_type( 0 ).ok().unwrap().as_ref().unwrap();
+    /// let data_type = type_any_ref.downcast_ref::<String>().unwrap();
+    /// assert_eq!( *data_type, "String" );
     /// ```
-    pub fn data_ref( &self, node_index: usize ) -> Result<&Vec<Box<dyn Any>>, TreeError> {
+    pub fn data_type( &self, node_index: usize ) -> Result<&Option<Box<dyn Any>>, TreeError> {
         let Some( index_node ) = self.node( node_index ) else {
             return Err( TreeError::RetrievingNode( node_index ) )
         };
-        if index_node.features & ALLOW_DATA != ALLOW_DATA/* !index_node.features.allow_data*/ {
-            return Err( TreeError::NoDataAllowed( node_index ) );
-        }
-        Ok( &index_node.data.as_ref().unwrap() )
+        Ok( &index_node.data_type )
     }
 
-    /// Obtain reference to the data type for the specified node `node_index`.
+    /// Check if the data type of the specified node `node_index` is of the type `T` and equal to `expected`.
+    /// 
+    /// `false` is returned when the node has no data type, or the data type is of a different type.
     /// 
     /// # Examples
     /// 
@@ -817,38 +4289,470 @@ impl Tree {
     /// 
     /// let mut tree = Tree::new();
     /// tree.insert( 514, ALLOW_CHILDREN | ALLOW_DATA, None, Some( Box::new( "String".to_string() ) ) ).ok();
-    /// let type_any_ref = tree.data_type( 0 ).ok().unwrap().as_ref().unwrap();
-    /// let data_type = type_any_ref.downcast_ref::<String>().unwrap();
+    /// assert!( tree.data_type_matches( 0, &"String".to_string() ).unwrap() );
+    /// assert!( !tree.data_type_matches( 0, &"i32".to_string() ).unwrap() );
+    /// assert!( !tree.data_type_matches( 0, &5i32 ).unwrap() );
+    /// ```
+    pub fn data_type_matches<T: Any + PartialEq>( &self, node_index: usize, expected: &T ) -> Result<bool, TreeError> {
+        let data_type = self.data_type( node_index )?.as_ref().and_then( |data_type| data_type.downcast_ref::<T>() );
+        Ok( data_type == Some( expected ) )
+    }
+
+    /// Take the data type out of the specified node `node_index`, leaving the data type as `None`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert( 514, ALLOW_CHILDREN | ALLOW_DATA, None, Some( Box::new( "String".to_string() ) ) ).ok();
+    /// let data_type = tree.take_data_type( 0 ).unwrap().unwrap().downcast::<String>().ok().unwrap();
     /// assert_eq!( *data_type, "String" );
+    /// assert!( tree.data_type( 0 ).unwrap().is_none(), "Data type is now `None`." );
     /// ```
-    pub fn data_type( &self, node_index: usize ) -> Result<&Option<Box<dyn Any>>, TreeError> {
-        let Some( index_node ) = self.node( node_index ) else {
+    pub fn take_data_type( &mut self, node_index: usize ) -> Result<Option<Box<dyn Any>>, TreeError> {
+        let Some( index_node ) = self.node_mut( node_index ) else {
             return Err( TreeError::RetrievingNode( node_index ) )
         };
-        Ok( &index_node.data_type )
+        let data_type = index_node.data_type.take();
+        self.notify( Mutation::Update( node_index ) );
+        Ok( data_type )
     }
 
     // -- Internal methods --
 
-    fn node( &self, node_index: usize ) -> Option<&Node> {
-        if let Some( option ) = self.nodes.get( node_index ) {
-            if let Some( node ) = option {
-                return Some( node );
+    // Deletes the subtree `node_index` from the tree, and returns the number of nodes deleted.
+    fn delete_subtree( &mut self, node_index: usize ) -> Result<usize, TreeError> {
+        let indices = self.subtree( node_index )?;
+        if let Some( parent ) = self.node( node_index ).unwrap().parent {
+            let Some( parent_node ) = self.node_mut( parent ) else {
+                return Err( TreeError::RetrievingNode( parent ) )
+            };
+            let children = parent_node.children.as_mut().unwrap();
+            let Some( position ) = children.iter().position( |&x| x == node_index ) else {
+                return Err( TreeError::MissingInParent( node_index, parent ) ); // Serious integrity issue.
+            };
+            children.remove( position );
+        }
+        for index in indices.iter() {
+            *self.nodes.get_mut( *index ).unwrap() = None;
+        }
+        if Some( node_index ) == self.root {
+            self.root = None;
+            self.nodes.clear();
+        }
+        for index in indices.iter() {
+            self.notify( Mutation::Delete( *index ) );
+        }
+        Ok( indices.len() )
+    }
+
+    // Obtain the height of the subtree `node_index`, where a leaf node has a height of 0.
+    fn height( &self, node_index: usize ) -> Result<usize, TreeError> {
+        Ok( self.heights( node_index )?[ node_index ] )
+    }
+
+    // Obtain the heights of all the nodes of the subtree `node_index`, indexed by the node indices. Nodes outside of the
+    // subtree have a height of 0.
+    fn heights( &self, node_index: usize ) -> Result<Vec<usize>, TreeError> {
+        let mut heights = vec![ 0; self.nodes.len() ];
+
+        // Visiting the nodes in reverse pre-order ensures the children's heights are known before their parent's.
+        for index in self.subtree( node_index )?.into_iter().rev() {
+            if let Some( children ) = self.node( index ).unwrap().children.as_ref() {
+                heights[ index ] = children.iter().map( |&child| heights[ child ] + 1 ).max().unwrap_or( 0 );
+            }
+        }
+        Ok( heights )
+    }
+
+    // Move the nodes of the subtree `node_index` into the `target` tree as a child of `target_parent` (ignored when the
+    // `target` tree is empty), and return the index of the subtree's node in the `target` tree. The subtree's node is
+    // not removed from its parent's children.
+    fn move_subtree_into(
+        &mut self,
+        node_index: usize,
+        target: &mut Tree,
+        target_parent: usize
+    ) -> Result<usize, TreeError> {
+        let mut indices = HashMap::<usize, usize>::new();
+
+        // Visiting the nodes in pre-order ensures the parents are moved before their children, in order.
+        for index in self.subtree( node_index )? {
+            let node = self.nodes[ index ].take().unwrap();
+            let parent = match node.parent {
+                Some( parent ) if index != node_index => indices[ &parent ],
+                _ => target_parent,
+            };
+            let target_index = target.insert( parent, node.features, node.node_type, node.data_type )?;
+            if let Some( data ) = node.data {
+                *target.data_mut( target_index )? = data;
+            }
+            indices.insert( index, target_index );
+        }
+        Ok( indices[ &node_index ] )
+    }
+
+    // Obtain the shape of the subtree `node_index`.
+    fn shape( &self, node_index: usize ) -> TreeShape {
+        let mut shapes = Vec::<Option<TreeShape>>::new();
+        shapes.resize_with( self.nodes.len(), || None );
+
+        // Visiting the nodes in reverse pre-order ensures the children's shapes are built before their parent's.
+        for index in self.subtree( node_index ).unwrap().into_iter().rev() {
+            let node = self.node( index ).unwrap();
+            let children = node.children.as_ref().map_or( Vec::new(), |children| {
+                children.iter().map( |&child| shapes[ child ].take().unwrap() ).collect()
+            } );
+            shapes[ index ] = Some( TreeShape::Node { features: node.features, children } );
+        }
+        shapes[ node_index ].take().unwrap()
+    }
+
+    // Notify the callback set by `set_on_mutation` (if any) of the `mutation`.
+    fn notify( &mut self, mutation: Mutation ) {
+        if let Some( hook ) = self.on_mutation.as_mut() {
+            hook( mutation );
+        }
+    }
+
+    // Obtain the indices of the nodes without a parent in ascending order.
+    fn parentless( &self ) -> impl Iterator<Item = usize> + '_ {
+        self.nodes
+            .iter()
+            .enumerate()
+            .filter( |( _, node )| node.as_ref().map_or( false, |node| node.parent.is_none() ) )
+            .map( |( index, _ )| index )
+    }
+
+    // Obtain the indices from `node_index` up to and including the root node.
+    fn path_to_root( &self, mut node_index: usize ) -> Result<Vec<usize>, TreeError> {
+        let mut path = Vec::<usize>::new();
+        loop {
+            let Some( node ) = self.node( node_index ) else {
+                return Err( TreeError::RetrievingNode( node_index ) )
+            };
+            path.push( node_index );
+            match node.parent {
+                Some( parent ) => node_index = parent,
+                None => return Ok( path )
+            }
+        }
+    }
+
+    // Obtain the indices of the subtree `node_index` in pre-order, starting with `node_index`.
+    fn subtree( &self, node_index: usize ) -> Result<Vec<usize>, TreeError> {
+        self.subtree_limited( node_index, usize::MAX )
+    }
+
+    // Obtain at most `limit` indices of the subtree `node_index` in pre-order, starting with `node_index`.
+    fn subtree_limited( &self, node_index: usize, limit: usize ) -> Result<Vec<usize>, TreeError> {
+        if !self.exists( node_index ) {
+            return Err( TreeError::RetrievingNode( node_index ) );
+        }
+        let mut indices = Vec::<usize>::new();
+        let mut stack = vec![ node_index ];
+        while indices.len() < limit {
+            let Some( index ) = stack.pop() else {
+                break;
+            };
+            let Some( node ) = self.node( index ) else {
+                return Err( TreeError::RetrievingNode( index ) )
+            };
+            indices.push( index );
+            if let Some( children ) = node.children.as_ref() {
+                stack.extend( children.iter().rev() );
             }
         }
+        Ok( indices )
+    }
+
+    fn node( &self, node_index: usize ) -> Option<&Node> {
+        if let Some( Some( node ) ) = self.nodes.get( node_index ) {
+            return Some( node );
+        }
         None
     }
 
     fn node_mut( &mut self, node_index: usize ) -> Option<&mut Node> {
-        if let Some( option ) = self.nodes.get_mut( node_index ) {
-            if let Some( node ) = option {
-                return Some( node );
+        if let Some( Some( node ) ) = self.nodes.get_mut( node_index ) {
+            return Some( node );
+        }
+        None
+    }
+}
+
+impl Default for Tree {
+    fn default() -> Self {
+        Tree::new()
+    }
+}
+
+/// Consume the tree, yielding the index and data of each node that allows data, in ascending order of the indices.
+/// 
+/// The nodes without the [`ALLOW_DATA`] feature are skipped, while the nodes allowing data yield their data even when
+/// it is empty.
+/// 
+/// # Examples
+/// 
+/// ```
+/// use tree::{Tree, ALLOW_CHILDREN, ALLOW_DATA};
+/// 
+/// let mut tree = Tree::new();
+/// tree.insert( 550, ALLOW_CHILDREN, None, None ).ok();
+/// tree.insert( 0, ALLOW_DATA, None, None ).ok();
+/// tree.data_mut( 1 ).unwrap().push( Box::new( "String data".to_string() ) );
+/// for ( index, data ) in tree {
+///     assert_eq!( index, 1 );
+///     assert_eq!( data.len(), 1 );
+/// }
+/// ```
+impl IntoIterator for Tree {
+    type Item = ( usize, Vec<Box<dyn Any>> );
+    type IntoIter = IntoIter;
+
+    fn into_iter( self ) -> Self::IntoIter {
+        IntoIter { nodes: self.nodes.into_iter().enumerate() }
+    }
+}
+
+/// An iterator that consumes the tree, yielding the index and data of each node that allows data.
+/// 
+/// Obtained from the [`into_iter`] method of the [`IntoIterator`] trait.
+/// 
+/// [`into_iter`]: IntoIterator::into_iter
+pub struct IntoIter {
+    nodes: core::iter::Enumerate<std::vec::IntoIter<Option<Node>>>,
+}
+
+impl Iterator for IntoIter {
+    type Item = ( usize, Vec<Box<dyn Any>> );
+
+    fn next( &mut self ) -> Option<Self::Item> {
+        for ( index, node ) in self.nodes.by_ref() {
+            if let Some( data ) = node.and_then( |node| node.data ) {
+                return Some( ( index, data ) );
             }
         }
         None
     }
 }
 
+/// A snapshot of a node's structure information, without the node type, data type and data.
+/// 
+/// Obtained from the [`flatten`] and [`node_summary`] methods.
+/// 
+/// [`flatten`]: Tree::flatten
+/// [`node_summary`]: Tree::node_summary
+#[derive( Debug, Clone, PartialEq, Eq )]
+pub struct NodeSummary {
+    pub index: usize,
+    pub depth: usize,
+    pub parent: Option<usize>,
+    pub features: u8,
+    pub data_len: usize,
+}
+
+/// The nested structure of a tree, without the node types, data types and data.
+/// 
+/// Obtained from the [`to_shape`] method.
+/// 
+/// # WARNING
+/// 
+/// Dropping a shape doesn't recurse, though the derived [`Clone`], [`PartialEq`] and [`Debug`] implementations recurse
+/// once per level of depth, thus may overflow the stack for very deep shapes.
+/// 
+/// [`to_shape`]: Tree::to_shape
+#[derive( Debug, Clone, PartialEq, Eq )]
+pub enum TreeShape {
+    /// A node with its features, and the shapes of its children in order.
+    Node {
+        features: u8,
+        children: Vec<TreeShape>,
+    },
+}
+
+impl Drop for TreeShape {
+    // Drains the children into an explicit stack, so that dropping a deep shape doesn't overflow the stack.
+    fn drop( &mut self ) {
+        let TreeShape::Node { children, .. } = self;
+        let mut stack = core::mem::take( children );
+        while let Some( mut shape ) = stack.pop() {
+            let TreeShape::Node { children, .. } = &mut shape;
+            stack.append( children );
+        }
+    }
+}
+
+/// A read-only view of a node of the tree, borrowing the tree.
+/// 
+/// Obtained from the [`view`] method.
+/// 
+/// [`view`]: Tree::view
+pub struct NodeView<'a> {
+    index: usize,
+    node: &'a Node,
+}
+
+impl<'a> NodeView<'a> {
+    /// Obtain the index of the node.
+    pub fn index( &self ) -> usize {
+        self.index
+    }
+
+    /// Obtain the features of the node.
+    pub fn features( &self ) -> u8 {
+        self.node.features
+    }
+
+    /// Obtain the parent of the node, which is `None` for the root node.
+    pub fn parent( &self ) -> Option<usize> {
+        self.node.parent
+    }
+
+    /// Obtain the children of the node, which is `None` when the node can't have children.
+    pub fn children( &self ) -> Option<&'a Vec<usize>> {
+        self.node.children.as_ref()
+    }
+
+    /// Obtain the node type of the node.
+    pub fn node_type( &self ) -> &'a Option<Box<dyn Any>> {
+        &self.node.node_type
+    }
+
+    /// Obtain the data of the node, which is `None` when the node can't have data.
+    pub fn data( &self ) -> Option<&'a Vec<Box<dyn Any>>> {
+        self.node.data.as_ref()
+    }
+
+    /// Obtain the data type of the node.
+    pub fn data_type( &self ) -> &'a Option<Box<dyn Any>> {
+        &self.node.data_type
+    }
+}
+
+/// A read-only cursor positioned at a node of the tree, borrowing the tree.
+/// 
+/// Navigating the cursor returns new cursors, where `None` is returned when there is no node to navigate to.
+/// 
+/// Obtained from the [`cursor`] method.
+/// 
+/// [`cursor`]: Tree::cursor
+#[derive( Clone, Copy )]
+pub struct Cursor<'a> {
+    tree: &'a Tree,
+    index: usize,
+}
+
+impl<'a> Cursor<'a> {
+    /// Obtain the index of the node.
+    pub fn index( &self ) -> usize {
+        self.index
+    }
+
+    /// Obtain a cursor for the parent of the node, which is `None` for the root node.
+    pub fn parent( &self ) -> Option<Cursor<'a>> {
+        let parent = self.tree.node( self.index )?.parent?;
+        Some( Cursor { tree: self.tree, index: parent } )
+    }
+
+    /// Obtain a cursor for the first child of the node, which is `None` when the node has no children.
+    pub fn first_child( &self ) -> Option<Cursor<'a>> {
+        let first = *self.tree.node( self.index )?.children.as_ref()?.first()?;
+        Some( Cursor { tree: self.tree, index: first } )
+    }
+
+    /// Obtain a cursor for the next sibling of the node, which is `None` for the last child or the root node.
+    pub fn next_sibling( &self ) -> Option<Cursor<'a>> {
+        let parent = self.tree.node( self.index )?.parent?;
+        let siblings = self.tree.node( parent )?.children.as_ref()?;
+        let position = siblings.iter().position( |&index| index == self.index )?;
+        let next = *siblings.get( position + 1 )?;
+        Some( Cursor { tree: self.tree, index: next } )
+    }
+
+    /// Obtain cursors for the children of the node in order, which is empty when the node has no children.
+    pub fn children( &self ) -> Vec<Cursor<'a>> {
+        self.tree
+            .node( self.index )
+            .and_then( |node| node.children.as_ref() )
+            .map_or( Vec::new(), |children| {
+                children.iter().map( |&index| Cursor { tree: self.tree, index } ).collect()
+            } )
+    }
+}
+
+/// A record of the previous parent and position of a moved node, for reversing the move.
+/// 
+/// Obtained from the [`reparent_recorded`] method, and used by the [`undo_move`] method.
+/// 
+/// [`reparent_recorded`]: Tree::reparent_recorded
+/// [`undo_move`]: Tree::undo_move
+#[derive( Debug, Clone, Copy, PartialEq, Eq )]
+pub struct MoveRecord {
+    pub node: usize,
+    pub old_parent: usize,
+    pub old_position: usize,
+}
+
+/// The position for placing a node within the children of its new parent.
+/// 
+/// Used for the `at` parameter of the [`reparent`] method.
+/// 
+/// [`reparent`]: Tree::reparent
+#[derive( Debug, Clone, Copy, PartialEq, Eq )]
+pub enum Position {
+    /// The first child.
+    Front,
+
+    /// The last child.
+    Back,
+
+    /// The specified position within the children.
+    At( usize ),
+}
+
+/// The decision of the `visit` closure of the [`search_pruned`] method for a visited node.
+/// 
+/// [`search_pruned`]: Tree::search_pruned
+#[derive( Debug, Clone, Copy, PartialEq, Eq )]
+pub enum Visit {
+    /// The node is the node being searched for.
+    Found,
+
+    /// The node is not the node being searched for, and its children are to be searched.
+    Descend,
+
+    /// The node is not the node being searched for, and its children are to be skipped.
+    Skip,
+}
+
+/// A mutation of the tree, of which the callback set by the [`set_on_mutation`] method is notified.
+/// 
+/// [`set_on_mutation`]: Tree::set_on_mutation
+#[derive( Debug, Clone, Copy, PartialEq, Eq )]
+pub enum Mutation {
+    /// The node was inserted.
+    Insert( usize ),
+
+    /// The node was deleted or taken.
+    Delete( usize ),
+
+    /// The node was moved to a new parent or position.
+    Move( usize ),
+
+    /// The data of the node was mutably accessed, and may have changed.
+    Data( usize ),
+
+    /// The order or the entries of the children of the node were changed, without inserting, deleting or moving a
+    /// node.
+    Children( usize ),
+
+    /// The features, node type or data type of the node was changed.
+    Update( usize ),
+}
+
 // Internal structs, functions, etc.
 
 struct Node {
@@ -859,3 +4763,146 @@ struct Node {
     data: Option<Vec<Box<dyn Any>>>,
     data_type: Option<Box<dyn Any>>,
 }
+
+// Tests that corrupt the internal structure of the tree, which can't be done through the public methods.
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0
+    // ├ 1
+    // │ ├ 2
+    // │ └ 3
+    // └ 4
+    //   └ 5
+    fn sample_tree() -> Tree {
+        let mut tree = Tree::new();
+        tree.insert( 0, ALLOW_CHILDREN, None, None ).unwrap();
+        tree.insert( 0, ALLOW_CHILDREN, None, None ).unwrap();
+        tree.insert( 1, ALLOW_DATA, None, None ).unwrap();
+        tree.insert( 1, ALLOW_DATA, None, None ).unwrap();
+        tree.insert( 0, ALLOW_CHILDREN, None, None ).unwrap();
+        tree.insert( 4, ALLOW_DATA, None, None ).unwrap();
+        tree
+    }
+
+    // Detach the subtree `node_index` from its parent, leaving it as a separate component.
+    fn detach( tree: &mut Tree, node_index: usize ) {
+        let parent = tree.node_mut( node_index ).unwrap().parent.take().unwrap();
+        tree.node_mut( parent ).unwrap().children.as_mut().unwrap().retain( |&child| child != node_index );
+    }
+
+    // Point the parent of `node_index` at `parent`, without adding the node to the children of `parent`.
+    fn corrupt_parent( tree: &mut Tree, node_index: usize, parent: usize ) {
+        tree.node_mut( node_index ).unwrap().parent = Some( parent );
+    }
+
+    #[test]
+    fn remove_unreachable_orphaned_subtree() {
+        let mut tree = sample_tree();
+        detach( &mut tree, 1 );
+        assert_eq!( tree.remove_unreachable(), 3, "Orphaned subtree of 3 nodes removed." );
+        assert!( !tree.exists( 1 ) && !tree.exists( 2 ) && !tree.exists( 3 ), "Orphaned nodes are gone." );
+        assert_eq!( tree.count(), 3, "Reachable nodes remain." );
+        assert_eq!( tree.children( 0 ).unwrap(), &vec![ 4 ] );
+        assert_eq!( tree.remove_unreachable(), 0, "Nothing left to remove." );
+    }
+
+    #[test]
+    fn trim_to_depth_missing_in_parent() {
+        let mut tree = sample_tree();
+        corrupt_parent( &mut tree, 2, 4 );
+        assert!(
+            matches!( tree.trim_to_depth( 1 ), Err( TreeError::MissingInParent( 2, 4 ) ) ),
+            "Integrity error is propagated."
+        );
+    }
+
+    #[test]
+    fn count_edges_forest() {
+        let mut tree = sample_tree();
+        detach( &mut tree, 4 );
+        assert_eq!( tree.count_edges(), 4, "The edge to node 4 is gone." );
+        assert_eq!( tree.count_edges(), tree.count() - 2, "Edges are nodes less the 2 components." );
+    }
+
+    #[test]
+    fn validate_node_corrupted() {
+        let mut tree = sample_tree();
+        corrupt_parent( &mut tree, 2, 4 );
+        assert!(
+            matches!( tree.validate_node( 2 ), Err( TreeError::MissingInParent( 2, 4 ) ) ),
+            "Node 2 is missing in the children of node 4."
+        );
+        assert!( matches!( tree.validate_node( 1 ), Err( TreeError::InvalidEdge( 2 ) ) ), "Child 2 has another parent." );
+        assert!( tree.validate_node( 4 ).is_ok(), "Node 4 is consistent." );
+        corrupt_parent( &mut tree, 0, 4 );
+        assert!( matches!( tree.validate_node( 0 ), Err( TreeError::RootHasNoParent( 0 ) ) ), "Root has a parent." );
+        tree.node_mut( 4 ).unwrap().children.as_mut().unwrap().push( 9 );
+        assert!( matches!( tree.validate_node( 4 ), Err( TreeError::RetrievingNode( 9 ) ) ), "Child 9 is dead." );
+    }
+
+    #[test]
+    fn repair_children_dead_child() {
+        let mut tree = sample_tree();
+        tree.node_mut( 4 ).unwrap().children.as_mut().unwrap().push( 9 );
+        assert_eq!( tree.repair_children(), 1, "The dead child index was removed." );
+        assert_eq!( tree.children( 4 ).unwrap(), &vec![ 5 ], "Live child remains." );
+        assert_eq!( tree.repair_children(), 0, "Nothing left to repair." );
+    }
+
+    #[test]
+    fn depth_cycle() {
+        let mut tree = sample_tree();
+        corrupt_parent( &mut tree, 1, 2 );
+        assert!( matches!( tree.depth( 3 ), Err( TreeError::CycleDetected( _ ) ) ), "Parents 1 and 2 form a cycle." );
+        assert_eq!( tree.depth( 5 ).unwrap(), 2, "Node 5 is outside the cycle." );
+    }
+
+    #[test]
+    fn rebuild_root_lost_root() {
+        let mut tree = sample_tree();
+        tree.root = None;
+        assert_eq!( tree.rebuild_root().unwrap(), 0, "The only orphan becomes the root." );
+        assert_eq!( tree.root, Some( 0 ), "Root is restored." );
+        detach( &mut tree, 4 );
+        tree.root = None;
+        assert!( matches!( tree.rebuild_root(), Err( TreeError::AmbiguousRoot( 2 ) ) ), "Nodes 0 and 4 are orphans." );
+        assert_eq!( tree.root, None, "Root is not guessed." );
+        corrupt_parent( &mut tree, 0, 1 );
+        corrupt_parent( &mut tree, 4, 5 );
+        assert!( matches!( tree.rebuild_root(), Err( TreeError::NoRootNode ) ), "Every node has a parent." );
+    }
+
+    #[test]
+    fn iter_forest_components() {
+        let mut tree = sample_tree();
+        detach( &mut tree, 1 );
+        let visited = tree.iter_forest().collect::<Vec<usize>>();
+        assert_eq!( visited, vec![ 0, 4, 5, 1, 2, 3 ], "Component 0, then component 1, each in pre-order." );
+        for index in 0..6 {
+            assert_eq!( visited.iter().filter( |&&x| x == index ).count(), 1, "Node {} visited once.", index );
+        }
+    }
+
+    #[test]
+    fn forest_summary_components() {
+        let mut tree = sample_tree();
+        detach( &mut tree, 1 );
+        let summary = tree.forest_summary();
+        assert_eq!( summary, vec![ ( 0, 3 ), ( 1, 3 ) ], "Two components of 3 nodes." );
+        assert_eq!( summary.iter().map( |( _, size )| size ).sum::<usize>(), tree.count(), "Sizes sum to count." );
+    }
+
+    #[test]
+    fn trim_empty_branches_missing_in_parent() {
+        let mut tree = sample_tree();
+        tree.data_mut( 2 ).unwrap().push( Box::new( "String data".to_string() ) );
+        corrupt_parent( &mut tree, 4, 1 );
+        assert!(
+            matches!( tree.trim_empty_branches(), Err( TreeError::MissingInParent( 4, 1 ) ) ),
+            "Integrity error is propagated."
+        );
+    }
+}
@@ -3,7 +3,39 @@
 
 // Various unit tests for `Tree`.
 
-use tree::{ Tree, ALLOW_CHILDREN, ALLOW_DATA, TreeError };
+use tree::{ Tree, ALLOW_CHILDREN, ALLOW_DATA, TreeError, Position, MoveRecord, Visit, Mutation, TreeShape };
+use core::any::Any;
+use std::rc::Rc;
+use std::cell::RefCell;
+
+// Build the tree of the statement: z = (x + y) / 2
+//
+// 0 Root
+// └ 1 Statement
+//   └ 2 Equal
+//     ├ 3 Leaf "z"
+//     └ 4 Divide
+//       ├ 5 Add
+//       │ ├ 6 Leaf "x"
+//       │ └ 7 Leaf "y"
+//       └ 8 Leaf "2"
+fn expression_tree() -> Tree {
+    let mut tree = Tree::new();
+    tree.insert( 0, ALLOW_CHILDREN, Some( Box::new( "Root".to_string() ) ), None ).ok();
+    tree.insert( 0, ALLOW_CHILDREN, Some( Box::new( "Statement".to_string() ) ), None ).ok();
+    tree.insert( 1, ALLOW_CHILDREN, Some( Box::new( "Equal".to_string() ) ), None ).ok();
+    let mut index = tree.insert( 2, ALLOW_DATA, Some( Box::new( "Leaf".to_string() ) ), None ).unwrap();
+    tree.data_mut( index ).unwrap().push( Box::new( "z".to_string() ) );
+    tree.insert( 2, ALLOW_CHILDREN, Some( Box::new( "Divide".to_string() ) ), None ).ok();
+    tree.insert( 4, ALLOW_CHILDREN, Some( Box::new( "Add".to_string() ) ), None ).ok();
+    index = tree.insert( 5, ALLOW_DATA, Some( Box::new( "Leaf".to_string() ) ), None ).unwrap();
+    tree.data_mut( index ).unwrap().push( Box::new( "x".to_string() ) );
+    index = tree.insert( 5, ALLOW_DATA, Some( Box::new( "Leaf".to_string() ) ), None ).unwrap();
+    tree.data_mut( index ).unwrap().push( Box::new( "y".to_string() ) );
+    index = tree.insert( 4, ALLOW_DATA, Some( Box::new( "Leaf".to_string() ) ), None ).unwrap();
+    tree.data_mut( index ).unwrap().push( Box::new( "2".to_string() ) );
+    tree
+}
 
 #[test]
 fn count() {
@@ -167,11 +199,7 @@ fn no_parent() {
         None,
         None,
     ).ok();
-    let mut result = false;
-    match tree.parent( 0 ).err().unwrap() {
-        TreeError::RootHasNoParent( _ ) => result = true,
-        _ => {}
-    };
+    let result = matches!( tree.parent( 0 ).err().unwrap(), TreeError::RootHasNoParent( _ ) );
     assert!( result, "Root node has no parent." );
 }
 
@@ -304,7 +332,7 @@ fn data_ref() {
     ).ok();
     tree.data_mut( 0 ).unwrap().push( Box::new( "String data".to_string() ) );
     let data_vec_ref = tree.data_ref( 0 ).ok().unwrap();
-    let data = data_vec_ref.get( 0 ).unwrap().downcast_ref::<String>().unwrap();
+    let data = data_vec_ref.first().unwrap().downcast_ref::<String>().unwrap();
     assert_eq!( *data, "String data".to_string() );
 }
 
@@ -434,6 +462,1816 @@ fn move_nodes() {
     ).ok();
     assert_eq!( tree.parent( 3 ).unwrap(), 1, "Parent of node 3 must be 1." );
     tree.move_nodes( 3, 2, None ).ok();
-    assert_eq!( tree.parent( 3 ).unwrap(), 1, "Parent of node 3 must be 2." );
+    assert_eq!( tree.parent( 3 ).unwrap(), 2, "Parent of node 3 must be 2." );
     assert_eq!( tree.parent( 4 ).unwrap(), 3, "Parent of node 4 must be 3." );
 }
+
+#[test]
+fn take_node_type() {
+    let mut tree = Tree::new();
+    tree.insert(
+        514,
+        ALLOW_CHILDREN | ALLOW_DATA,
+        Some( Box::new( "node type 2".to_string() ) ),
+        None,
+    ).ok();
+    let node_type = tree.take_node_type( 0 ).ok().unwrap().unwrap().downcast::<String>().ok().unwrap();
+    assert_eq!( *node_type, "node type 2" );
+    assert!( tree.node_type( 0 ).ok().unwrap().is_none(), "Node type is now `None`." );
+}
+
+#[test]
+fn take_data_type() {
+    let mut tree = Tree::new();
+    tree.insert(
+        514,
+        ALLOW_CHILDREN | ALLOW_DATA,
+        None,
+        Some( Box::new( "String".to_string() ) ),
+    ).ok();
+    let data_type = tree.take_data_type( 0 ).ok().unwrap().unwrap().downcast::<String>().ok().unwrap();
+    assert_eq!( *data_type, "String" );
+    assert!( tree.data_type( 0 ).ok().unwrap().is_none(), "Data type is now `None`." );
+}
+
+#[test]
+fn count_subtree_with_feature() {
+    let tree = expression_tree();
+    assert_eq!( tree.count_subtree_with_feature( 4, ALLOW_DATA ).unwrap(), 3, "Divide has 3 data nodes." );
+    assert_eq!( tree.count_subtree_with_feature( 4, ALLOW_CHILDREN ).unwrap(), 2, "Divide has 2 structure nodes." );
+    assert!( matches!( tree.count_subtree_with_feature( 20, ALLOW_DATA ), Err( TreeError::RetrievingNode( 20 ) ) ) );
+}
+
+#[test]
+fn flatten() {
+    let tree = expression_tree();
+    let summaries = tree.flatten();
+    assert_eq!( summaries.len(), tree.count(), "Has a summary for each node." );
+    assert_eq!( summaries[ 0 ].index, 0, "First summary is the root node." );
+    assert_eq!( summaries[ 0 ].depth, 0, "Root node is at depth 0." );
+    assert_eq!( summaries[ 0 ].parent, None, "Root node has no parent." );
+    assert!( Tree::new().flatten().is_empty(), "Empty tree has no summaries." );
+}
+
+#[test]
+fn insert_root_if_empty_creates_root() {
+    let mut tree = Tree::new();
+    let root = tree.insert_root_if_empty(
+        ALLOW_CHILDREN,
+        Some( Box::new( "Root".to_string() ) ),
+        None,
+    );
+    assert_eq!( root, 0, "Root node is index 0." );
+    assert_eq!( tree.count(), 1, "1 node is present." );
+    assert!( tree.parent( root ).is_err(), "Created node is the root." );
+}
+
+#[test]
+fn insert_root_if_empty_existing_root() {
+    let mut tree = expression_tree();
+    let root = tree.insert_root_if_empty(
+        ALLOW_DATA,
+        None,
+        None,
+    );
+    assert_eq!( root, 0, "Existing root node is index 0." );
+    assert_eq!( tree.count(), 9, "No node was inserted." );
+    assert_eq!( *tree.features( root ).unwrap(), ALLOW_CHILDREN, "Root node features are unchanged." );
+}
+
+#[test]
+fn subtree_data_refs() {
+    let tree = expression_tree();
+    let data_refs = tree.subtree_data_refs( 4 ).unwrap();
+    assert_eq!(
+        data_refs.len(),
+        tree.count_subtree_with_feature( 4, ALLOW_DATA ).unwrap(),
+        "Has an entry for each data node."
+    );
+    let indices = data_refs.iter().map( |( index, _ )| *index ).collect::<Vec<usize>>();
+    assert_eq!( indices, vec![ 6, 7, 8 ], "Data nodes in pre-order." );
+    let data = data_refs[ 0 ].1.first().unwrap().downcast_ref::<String>().unwrap();
+    assert_eq!( *data, "x", "First data node is `x`." );
+}
+
+#[test]
+fn remove_unreachable() {
+    let mut tree = expression_tree();
+    assert_eq!( tree.remove_unreachable(), 0, "All nodes are reachable from the root." );
+    assert_eq!( tree.count(), 9, "9 nodes are present." );
+    tree.clear();
+    assert_eq!( tree.remove_unreachable(), 0, "Empty tree has nothing to remove." );
+}
+
+#[test]
+fn replace_node_in_place_allow_data() {
+    let mut tree = expression_tree();
+    assert!( tree.data_ref( 2 ).is_err(), "Node 2 has no data." );
+    tree.replace_node_in_place(
+        2,
+        ALLOW_CHILDREN | ALLOW_DATA,
+        Some( Box::new( "Assign".to_string() ) ),
+        None,
+    ).ok();
+    assert!( tree.data_ref( 2 ).unwrap().is_empty(), "Node 2 now allows data." );
+    assert_eq!( tree.children( 2 ).unwrap(), &vec![ 3, 4 ], "Children are unchanged." );
+    let node_type = tree.node_type( 2 ).unwrap().as_ref().unwrap().downcast_ref::<String>().unwrap();
+    assert_eq!( *node_type, "Assign", "Node type is replaced." );
+}
+
+#[test]
+fn replace_node_in_place_disallow_data() {
+    let mut tree = expression_tree();
+    assert_eq!( tree.data_ref( 3 ).unwrap().len(), 1, "Node 3 has data." );
+    tree.replace_node_in_place(
+        3,
+        ALLOW_CHILDREN,
+        None,
+        None,
+    ).ok();
+    assert!( matches!( tree.data_ref( 3 ), Err( TreeError::NoDataAllowed( 3 ) ) ), "Node 3 has no data." );
+    assert!( tree.children( 3 ).unwrap().is_empty(), "Node 3 now allows children." );
+    assert_eq!( tree.parent( 3 ).unwrap(), 2, "Parent is unchanged." );
+}
+
+#[test]
+fn replace_node_in_place_has_children() {
+    let mut tree = expression_tree();
+    let result = tree.replace_node_in_place(
+        4,
+        ALLOW_DATA,
+        None,
+        None,
+    );
+    assert!( matches!( result, Err( TreeError::HasChildren( 4 ) ) ), "Node 4 still has children." );
+    assert_eq!( *tree.features( 4 ).unwrap(), ALLOW_CHILDREN, "Features are unchanged." );
+}
+
+#[test]
+fn path_between() {
+    let mut tree = Tree::new();
+    tree.insert( 0, ALLOW_CHILDREN, None, None ).ok();
+    tree.insert( 0, ALLOW_CHILDREN, None, None ).ok();
+    tree.insert( 0, ALLOW_CHILDREN, None, None ).ok();
+    tree.insert( 1, ALLOW_DATA, None, None ).ok();
+    tree.insert( 1, ALLOW_DATA, None, None ).ok();
+    tree.insert( 2, ALLOW_DATA, None, None ).ok();
+    tree.insert( 2, ALLOW_DATA, None, None ).ok();
+    assert_eq!( tree.path_between( 3, 6 ).unwrap(), vec![ 3, 1, 0, 2, 6 ], "Route through grandparent." );
+    assert_eq!( tree.path_between( 3, 4 ).unwrap(), vec![ 3, 1, 4 ], "Route through parent." );
+    assert_eq!( tree.path_between( 0, 5 ).unwrap(), vec![ 0, 2, 5 ], "Route down from root." );
+    assert_eq!( tree.path_between( 5, 5 ).unwrap(), vec![ 5 ], "Route to itself." );
+    assert!( matches!( tree.path_between( 3, 20 ), Err( TreeError::RetrievingNode( 20 ) ) ) );
+}
+
+#[test]
+fn trim_to_depth() {
+    let mut tree = Tree::new();
+    tree.insert( 0, ALLOW_CHILDREN, None, None ).ok();
+    tree.insert( 0, ALLOW_CHILDREN, None, None ).ok();
+    tree.insert( 0, ALLOW_CHILDREN, None, None ).ok();
+    tree.insert( 1, ALLOW_DATA, None, None ).ok();
+    tree.insert( 1, ALLOW_DATA, None, None ).ok();
+    tree.insert( 2, ALLOW_DATA, None, None ).ok();
+    assert_eq!( tree.trim_to_depth( 1 ).unwrap(), 3, "Deleted 3 nodes." );
+    assert_eq!( tree.count(), 3, "3 nodes are present." );
+    assert!( tree.flatten().iter().all( |summary| summary.depth <= 1 ), "No nodes deeper than 1." );
+    assert!( tree.children( 1 ).unwrap().is_empty(), "Node 1 has no children." );
+    assert!( tree.children( 2 ).unwrap().is_empty(), "Node 2 has no children." );
+    assert_eq!( tree.trim_to_depth( 0 ).unwrap(), 2, "Deleted 2 nodes." );
+    assert_eq!( tree.count(), 1, "Root node is kept." );
+}
+
+#[test]
+fn iter_with_path() {
+    let tree = expression_tree();
+    let items = tree.iter_with_path().collect::<Vec<( usize, Vec<usize> )>>();
+    assert_eq!( items.len(), tree.count(), "Every node is visited." );
+    assert_eq!( items[ 0 ], ( 0, vec![ 0 ] ), "Root node path is itself." );
+    let ( _, path ) = items.iter().find( |( index, _ )| *index == 7 ).unwrap();
+    assert_eq!( *path, vec![ 0, 1, 2, 4, 5, 7 ], "Path of leaf `y`." );
+    assert_eq!( *path.last().unwrap(), 7, "Path ends with the leaf." );
+}
+
+#[test]
+fn data_ref_all() {
+    let tree = expression_tree();
+    let data_refs = tree.data_ref_all();
+    assert_eq!(
+        data_refs.len(),
+        tree.count_subtree_with_feature( 0, ALLOW_DATA ).unwrap(),
+        "Has an entry for each data node."
+    );
+    let indices = data_refs.iter().map( |( index, _ )| *index ).collect::<Vec<usize>>();
+    assert_eq!( indices, vec![ 3, 6, 7, 8 ], "Entries are in ascending order." );
+}
+
+#[test]
+fn set_features() {
+    let mut tree = expression_tree();
+    tree.set_features( 5, ALLOW_CHILDREN | ALLOW_DATA ).ok();
+    assert_eq!( *tree.features( 5 ).unwrap(), ALLOW_CHILDREN | ALLOW_DATA, "Features are changed." );
+    assert!( tree.data_ref( 5 ).unwrap().is_empty(), "Node 5 now allows data." );
+    assert_eq!( tree.children( 5 ).unwrap(), &vec![ 6, 7 ], "Children are unchanged." );
+    tree.set_features( 5, ALLOW_CHILDREN ).ok();
+    assert!( matches!( tree.data_ref( 5 ), Err( TreeError::NoDataAllowed( 5 ) ) ), "Data is disallowed again." );
+}
+
+#[test]
+fn set_features_has_children() {
+    let mut tree = expression_tree();
+    let result = tree.set_features( 5, ALLOW_DATA );
+    assert!( matches!( result, Err( TreeError::HasChildren( 5 ) ) ), "Node 5 still has children." );
+    assert_eq!( *tree.features( 5 ).unwrap(), ALLOW_CHILDREN, "Features are unchanged." );
+}
+
+#[test]
+fn set_features_has_data() {
+    let mut tree = expression_tree();
+    let result = tree.set_features( 6, ALLOW_CHILDREN );
+    assert!( matches!( result, Err( TreeError::HasData( 6 ) ) ), "Node 6 still has data." );
+    assert_eq!( tree.data_ref( 6 ).unwrap().len(), 1, "Data is unchanged." );
+}
+
+#[test]
+fn set_features_allow_children() {
+    let mut tree = expression_tree();
+    tree.data_mut( 6 ).unwrap().clear();
+    tree.set_features( 6, ALLOW_CHILDREN ).ok();
+    assert!( tree.children( 6 ).unwrap().is_empty(), "Node 6 now allows children." );
+    assert!( tree.insert( 6, ALLOW_DATA, None, None ).is_ok(), "Child can be inserted." );
+}
+
+#[test]
+fn count_between_depths() {
+    let mut tree = Tree::new();
+    tree.insert( 0, ALLOW_CHILDREN, None, None ).ok();
+    tree.insert( 0, ALLOW_CHILDREN, None, None ).ok();
+    tree.insert( 0, ALLOW_CHILDREN, None, None ).ok();
+    tree.insert( 1, ALLOW_CHILDREN, None, None ).ok();
+    tree.insert( 1, ALLOW_CHILDREN, None, None ).ok();
+    tree.insert( 2, ALLOW_CHILDREN, None, None ).ok();
+    tree.insert( 3, ALLOW_DATA, None, None ).ok();
+    tree.insert( 5, ALLOW_DATA, None, None ).ok();
+    assert_eq!( tree.count_between_depths( 1, 2 ), 5, "Has 5 nodes at depths 1 to 2." );
+    assert_eq!( tree.count_between_depths( 0, 3 ), tree.count(), "All nodes are at depths 0 to 3." );
+    assert_eq!( tree.count_between_depths( 3, 3 ), 2, "Has 2 nodes at depth 3." );
+    assert_eq!( tree.count_between_depths( 2, 1 ), 0, "Empty range." );
+}
+
+#[test]
+fn find_child_by_type() {
+    let mut tree = expression_tree();
+    tree.insert( 2, ALLOW_DATA, None, None ).ok();
+    let child = tree.find_child_by_type( 2, &"Leaf".to_string() ).unwrap();
+    assert_eq!( child, Some( 3 ), "First leaf child is index 3." );
+    let child = tree.find_child_by_type( 4, &"Add".to_string() ).unwrap();
+    assert_eq!( child, Some( 5 ), "Add child is index 5." );
+    let child = tree.find_child_by_type( 2, &"Root".to_string() ).unwrap();
+    assert_eq!( child, None, "No root child." );
+    let child = tree.find_child_by_type( 2, &7u8 ).unwrap();
+    assert_eq!( child, None, "No child with `u8` node type." );
+    assert!( matches!( tree.find_child_by_type( 3, &"Leaf".to_string() ), Err( TreeError::NoChildrenAllowed( 3 ) ) ) );
+}
+
+#[test]
+fn node_type_downcast_ref() {
+    let mut tree = Tree::new();
+    tree.insert(
+        514,
+        ALLOW_CHILDREN | ALLOW_DATA,
+        Some( Box::new( "node type 2".to_string() ) ),
+        None,
+    ).ok();
+    let node_type = tree.node_type_downcast_ref::<String>( 0 ).unwrap().unwrap();
+    assert_eq!( *node_type, "node type 2" );
+}
+
+#[test]
+fn node_type_downcast_ref_none() {
+    let mut tree = Tree::new();
+    tree.insert(
+        514,
+        ALLOW_CHILDREN | ALLOW_DATA,
+        None,
+        None,
+    ).ok();
+    assert!( tree.node_type_downcast_ref::<String>( 0 ).unwrap().is_none(), "Node has no node type." );
+}
+
+#[test]
+fn node_type_downcast_ref_wrong_type() {
+    let mut tree = Tree::new();
+    tree.insert(
+        514,
+        ALLOW_CHILDREN | ALLOW_DATA,
+        Some( Box::new( "node type 2".to_string() ) ),
+        None,
+    ).ok();
+    let result = tree.node_type_downcast_ref::<usize>( 0 );
+    assert!( matches!( result, Err( TreeError::DowncastFailed( 0 ) ) ), "Node type is not `usize`." );
+}
+
+#[test]
+fn bulk_delete() {
+    let mut tree = expression_tree();
+    assert_eq!( tree.bulk_delete( &[ 6, 7, 3, 7 ] ).unwrap(), 3, "Deleted 3 nodes." );
+    assert_eq!( tree.count(), 6, "6 nodes are present." );
+    assert!( tree.children( 5 ).unwrap().is_empty(), "Node 5 has no children." );
+    assert_eq!( tree.children( 2 ).unwrap(), &vec![ 4 ], "Node 2 has 1 child." );
+}
+
+#[test]
+fn bulk_delete_has_children() {
+    let mut tree = expression_tree();
+    let result = tree.bulk_delete( &[ 6, 7, 4 ] );
+    assert!( matches!( result, Err( TreeError::HasChildren( 4 ) ) ), "Node 4 still has children." );
+    assert_eq!( tree.count(), 9, "No nodes were deleted." );
+}
+
+#[test]
+fn child_position() {
+    let mut tree = expression_tree();
+    tree.insert( 4, ALLOW_DATA, None, None ).ok();
+    assert_eq!( tree.child_position( 5 ).unwrap(), Some( 0 ), "Node 5 is the first child." );
+    assert_eq!( tree.child_position( 8 ).unwrap(), Some( 1 ), "Node 8 is the second child." );
+    assert_eq!( tree.child_position( 9 ).unwrap(), Some( 2 ), "Node 9 is the third child." );
+}
+
+#[test]
+fn child_position_root() {
+    let tree = expression_tree();
+    assert_eq!( tree.child_position( 0 ).unwrap(), None, "Root node has no position." );
+    assert!( matches!( tree.child_position( 20 ), Err( TreeError::RetrievingNode( 20 ) ) ) );
+}
+
+#[test]
+fn merge_data_from() {
+    let mut tree = expression_tree();
+    tree.data_mut( 7 ).unwrap().push( Box::new( "w".to_string() ) );
+    tree.merge_data_from( 6, 7 ).ok();
+    assert_eq!( tree.data_ref( 6 ).unwrap().len(), 3, "Node 6 has 3 data items." );
+    assert!( tree.data_ref( 7 ).unwrap().is_empty(), "Node 7 has no data." );
+    let data = tree.data_ref( 6 ).unwrap().last().unwrap().downcast_ref::<String>().unwrap();
+    assert_eq!( *data, "w", "Merged data is appended." );
+    tree.merge_data_from( 6, 6 ).ok();
+    assert_eq!( tree.data_ref( 6 ).unwrap().len(), 3, "Merging into itself is a no-op." );
+    assert!( matches!( tree.merge_data_from( 5, 6 ), Err( TreeError::NoDataAllowed( 5 ) ) ) );
+    assert_eq!( tree.data_ref( 6 ).unwrap().len(), 3, "Data is unchanged on error." );
+}
+
+#[test]
+fn iter_siblings_including() {
+    let mut tree = expression_tree();
+    tree.insert( 4, ALLOW_DATA, None, None ).ok();
+    assert_eq!( tree.iter_siblings_including( 8 ).unwrap(), vec![ 5, 8, 9 ], "All children of node 4." );
+    assert_eq!( tree.iter_siblings_including( 5 ).unwrap(), vec![ 5, 8, 9 ], "Same for the first child." );
+    assert_eq!( tree.iter_siblings_including( 0 ).unwrap(), vec![ 0 ], "Root node is its only sibling." );
+}
+
+#[test]
+fn deepest_leaf() {
+    let tree = expression_tree();
+    assert_eq!( tree.deepest_leaf(), Some( ( 6, 5 ) ), "Leaf `x` is the first deepest leaf." );
+    assert_eq!( Tree::new().deepest_leaf(), None, "Empty tree has no leaves." );
+}
+
+#[test]
+fn deepest_leaf_unbalanced() {
+    let mut tree = Tree::new();
+    tree.insert( 0, ALLOW_CHILDREN, None, None ).ok();
+    tree.insert( 0, ALLOW_DATA, None, None ).ok();
+    tree.insert( 0, ALLOW_CHILDREN, None, None ).ok();
+    tree.insert( 2, ALLOW_DATA, None, None ).ok();
+    tree.insert( 2, ALLOW_CHILDREN, None, None ).ok();
+    tree.insert( 4, ALLOW_CHILDREN, None, None ).ok();
+    assert_eq!( tree.deepest_leaf(), Some( ( 5, 3 ) ), "Node 5 is on the longest branch." );
+}
+
+#[test]
+fn balance_factor() {
+    let tree = expression_tree();
+    assert_eq!( tree.balance_factor( 4 ).unwrap(), 1, "Divide is left heavy." );
+    assert_eq!( tree.balance_factor( 2 ).unwrap(), -2, "Equal is right heavy." );
+    assert_eq!( tree.balance_factor( 5 ).unwrap(), 0, "Add is balanced." );
+    assert_eq!( tree.balance_factor( 1 ).unwrap(), 0, "Statement has 1 child." );
+    assert_eq!( tree.balance_factor( 3 ).unwrap(), 0, "Leaf has no children." );
+}
+
+#[test]
+fn take_all_of_type() {
+    let mut tree = expression_tree();
+    tree.data_mut( 6 ).unwrap().push( Box::new( 1i32 ) );
+    tree.data_mut( 8 ).unwrap().insert( 0, Box::new( 2i32 ) );
+    tree.data_mut( 8 ).unwrap().push( Box::new( 3i32 ) );
+    let taken = tree.take_all_of_type::<i32>();
+    assert_eq!( taken, vec![ ( 6, 1 ), ( 8, 2 ), ( 8, 3 ) ], "Only `i32` data is taken." );
+    assert_eq!( tree.data_ref( 6 ).unwrap().len(), 1, "Node 6 keeps its string." );
+    assert_eq!( tree.data_ref( 8 ).unwrap().len(), 1, "Node 8 keeps its string." );
+    let data = tree.data_ref( 8 ).unwrap().first().unwrap().downcast_ref::<String>().unwrap();
+    assert_eq!( *data, "2", "String data is unchanged." );
+    assert!( tree.take_all_of_type::<i32>().is_empty(), "No `i32` data is left." );
+}
+
+#[test]
+fn insert_subtree_from_edges() {
+    let mut tree = expression_tree();
+    let edges = [ ( 1, None ), ( 2, Some( 1 ) ), ( 3, Some( 1 ) ) ];
+    let root = tree.insert_subtree_from_edges( 4, &edges, ALLOW_CHILDREN | ALLOW_DATA ).unwrap();
+    assert_eq!( tree.count(), 12, "12 nodes are present." );
+    assert_eq!( tree.parent( root ).unwrap(), 4, "Subtree root is a child of node 4." );
+    assert_eq!( tree.last( 4 ).unwrap(), root, "Subtree root is the last child of node 4." );
+    assert_eq!( tree.dfs_order( root ).unwrap(), vec![ root, root + 1, root + 2 ], "Created in pre-order." );
+    let children = tree.children( root ).unwrap().clone();
+    assert_eq!( children.len(), 2, "Subtree root has 2 children." );
+    for child in children {
+        assert_eq!( tree.parent( child ).unwrap(), root, "Parent is subtree root." );
+    }
+}
+
+#[test]
+fn insert_subtree_from_edges_invalid() {
+    let mut tree = expression_tree();
+    let result = tree.insert_subtree_from_edges( 4, &[ ( 1, None ), ( 2, None ) ], ALLOW_CHILDREN );
+    assert!( matches!( result, Err( TreeError::AmbiguousRoot( 2 ) ) ), "Has 2 roots." );
+    let result = tree.insert_subtree_from_edges( 4, &[ ( 1, None ), ( 2, Some( 5 ) ) ], ALLOW_CHILDREN );
+    assert!( matches!( result, Err( TreeError::InvalidEdge( 2 ) ) ), "Parent is missing." );
+    let result = tree.insert_subtree_from_edges(
+        4,
+        &[ ( 1, None ), ( 2, Some( 3 ) ), ( 3, Some( 2 ) ) ],
+        ALLOW_CHILDREN
+    );
+    assert!( matches!( result, Err( TreeError::InvalidEdge( 2 ) ) ), "Cycle is not connected to root." );
+    let result = tree.insert_subtree_from_edges( 3, &[ ( 1, None ) ], ALLOW_CHILDREN );
+    assert!( matches!( result, Err( TreeError::NoChildrenAllowed( 3 ) ) ), "Node 3 is a leaf." );
+    let result = tree.insert_subtree_from_edges( 4, &[ ( 10, None ), ( 11, Some( 10 ) ) ], ALLOW_DATA );
+    assert!( matches!( result, Err( TreeError::InvalidEdge( 11 ) ) ), "Local node 10 can't have children." );
+    assert_eq!( tree.count(), 9, "No nodes were created." );
+}
+
+#[test]
+fn reparent_front() {
+    let mut tree = expression_tree();
+    tree.reparent( 8, 5, Position::Front ).ok();
+    assert_eq!( tree.parent( 8 ).unwrap(), 5, "Parent of node 8 is 5." );
+    assert_eq!( tree.children( 5 ).unwrap(), &vec![ 8, 6, 7 ], "Node 8 is the first child." );
+    assert_eq!( tree.children( 4 ).unwrap(), &vec![ 5 ], "Node 8 is removed from node 4." );
+}
+
+#[test]
+fn reparent_back() {
+    let mut tree = expression_tree();
+    tree.reparent( 5, 2, Position::Back ).ok();
+    assert_eq!( tree.parent( 5 ).unwrap(), 2, "Parent of node 5 is 2." );
+    assert_eq!( tree.children( 2 ).unwrap(), &vec![ 3, 4, 5 ], "Node 5 is the last child." );
+    assert_eq!( tree.parent( 6 ).unwrap(), 5, "Subtree is moved with node 5." );
+}
+
+#[test]
+fn reparent_at() {
+    let mut tree = expression_tree();
+    tree.reparent( 3, 5, Position::At( 1 ) ).ok();
+    assert_eq!( tree.children( 5 ).unwrap(), &vec![ 6, 3, 7 ], "Node 3 is the second child." );
+    tree.reparent( 6, 5, Position::At( 2 ) ).ok();
+    assert_eq!( tree.children( 5 ).unwrap(), &vec![ 3, 7, 6 ], "Node 6 is repositioned within node 5." );
+    let result = tree.reparent( 6, 5, Position::At( 3 ) );
+    assert!( matches!( result, Err( TreeError::ExceedsChildren( 3, 5 ) ) ), "Position 3 exceeds children." );
+}
+
+#[test]
+fn reparent_empty_destination() {
+    let mut tree = expression_tree();
+    tree.set_features( 3, ALLOW_CHILDREN | ALLOW_DATA ).ok();
+    tree.reparent( 6, 3, Position::Front ).ok();
+    assert_eq!( tree.children( 3 ).unwrap(), &vec![ 6 ], "Front of empty destination." );
+    tree.set_features( 8, ALLOW_CHILDREN | ALLOW_DATA ).ok();
+    tree.reparent( 7, 8, Position::Back ).ok();
+    assert_eq!( tree.children( 8 ).unwrap(), &vec![ 7 ], "Back of empty destination." );
+    assert!( tree.children( 5 ).unwrap().is_empty(), "Node 5 has no children." );
+}
+
+#[test]
+fn reparent_is_ancestor_of() {
+    let mut tree = expression_tree();
+    let result = tree.reparent( 4, 5, Position::Back );
+    assert!( matches!( result, Err( TreeError::IsAncestorOf( 5, 4 ) ) ), "Node 4 is an ancestor of node 5." );
+    let result = tree.reparent( 3, 6, Position::Back );
+    assert!( matches!( result, Err( TreeError::NoChildrenAllowed( 6 ) ) ), "Node 6 can't have children." );
+    assert_eq!( tree.parent( 4 ).unwrap(), 2, "Node 4 is not moved." );
+}
+
+#[test]
+fn subtree_leaves() {
+    let tree = expression_tree();
+    assert_eq!( tree.subtree_leaves( 4 ).unwrap(), vec![ 6, 7, 8 ], "Leaves of Divide." );
+    assert_eq!( tree.subtree_leaves( 0 ).unwrap(), vec![ 3, 6, 7, 8 ], "Leaves of the tree." );
+    assert_eq!( tree.subtree_leaves( 3 ).unwrap(), vec![ 3 ], "Leaf is its own leaf." );
+    assert!( matches!( tree.subtree_leaves( 20 ), Err( TreeError::RetrievingNode( 20 ) ) ) );
+}
+
+#[test]
+fn count_edges() {
+    let mut tree = expression_tree();
+    assert_eq!( tree.count_edges(), tree.count() - 1, "Has 1 edge less than nodes." );
+    tree.bulk_delete( &[ 6, 7 ] ).ok();
+    assert_eq!( tree.count_edges(), tree.count() - 1, "Still 1 edge less than nodes after delete." );
+    assert_eq!( Tree::new().count_edges(), 0, "Empty tree has no edges." );
+}
+
+#[test]
+fn nearest_ancestor_with() {
+    let tree = expression_tree();
+    let is_type = |node_type: &str| {
+        let node_type = node_type.to_string();
+        move |index: usize, tree: &Tree| {
+            tree.node_type( index ).unwrap().as_ref().map_or( false, |value| {
+                value.downcast_ref::<String>().map_or( false, |value| *value == node_type )
+            } )
+        }
+    };
+    assert_eq!( tree.nearest_ancestor_with( 7, is_type( "Statement" ) ).unwrap(), Some( 1 ), "Statement is node 1." );
+    assert_eq!( tree.nearest_ancestor_with( 7, is_type( "Add" ) ).unwrap(), Some( 5 ), "Add is the parent." );
+    assert_eq!( tree.nearest_ancestor_with( 7, is_type( "Leaf" ) ).unwrap(), None, "Node itself is excluded." );
+    assert_eq!( tree.nearest_ancestor_with( 0, is_type( "Root" ) ).unwrap(), None, "Root node has no ancestors." );
+    assert!( matches!( tree.nearest_ancestor_with( 20, is_type( "Root" ) ), Err( TreeError::RetrievingNode( 20 ) ) ) );
+}
+
+#[test]
+fn children_data_refs() {
+    let tree = expression_tree();
+    let data_refs = tree.children_data_refs( 2 ).unwrap();
+    assert_eq!( data_refs.len(), 2, "Has 2 children." );
+    assert_eq!( data_refs[ 0 ].0, 3, "First child is node 3." );
+    let data = data_refs[ 0 ].1.unwrap().first().unwrap().downcast_ref::<String>().unwrap();
+    assert_eq!( *data, "z", "Node 3 has data `z`." );
+    assert_eq!( data_refs[ 1 ].0, 4, "Second child is node 4." );
+    assert!( data_refs[ 1 ].1.is_none(), "Node 4 doesn't allow data." );
+    assert!( matches!( tree.children_data_refs( 3 ), Err( TreeError::NoChildrenAllowed( 3 ) ) ) );
+}
+
+#[test]
+fn collapse() {
+    let mut tree = expression_tree();
+    let data = tree.collapse( 4 ).unwrap();
+    assert!( data.is_none(), "Node 4 has no data." );
+    assert!( !tree.exists( 4 ), "Node 4 is deleted." );
+    assert_eq!( tree.children( 2 ).unwrap(), &vec![ 3, 5, 8 ], "Children of node 4 replace node 4." );
+    assert_eq!( tree.parent( 5 ).unwrap(), 2, "Parent of node 5 is 2." );
+    assert_eq!( tree.parent( 8 ).unwrap(), 2, "Parent of node 8 is 2." );
+    assert_eq!( tree.count(), 8, "8 nodes are present." );
+}
+
+#[test]
+fn collapse_leaf_and_root() {
+    let mut tree = expression_tree();
+    let mut data = tree.collapse( 3 ).unwrap().unwrap();
+    let data = data.pop().unwrap().downcast::<String>().ok().unwrap();
+    assert_eq!( *data, "z", "Data of node 3 is returned." );
+    assert_eq!( tree.children( 2 ).unwrap(), &vec![ 4 ], "Node 3 is removed." );
+    assert!( matches!( tree.collapse( 0 ), Err( TreeError::RootHasNoParent( 0 ) ) ) );
+}
+
+#[test]
+fn wrap() {
+    let mut tree = expression_tree();
+    let index = tree.wrap( 5, ALLOW_CHILDREN, Some( Box::new( "Group".to_string() ) ), None ).unwrap();
+    assert_eq!( index, 9, "Created node is index 9." );
+    assert_eq!( tree.children( 4 ).unwrap(), &vec![ 9, 8 ], "Created node takes the place of node 5." );
+    assert_eq!( tree.parent( 9 ).unwrap(), 4, "Parent of node 9 is 4." );
+    assert_eq!( tree.children( 9 ).unwrap(), &vec![ 5 ], "Node 5 is the only child." );
+    assert_eq!( tree.parent( 5 ).unwrap(), 9, "Parent of node 5 is 9." );
+    assert_eq!( tree.depth( 6 ).unwrap(), 6, "Depth of node 6 increased." );
+}
+
+#[test]
+fn wrap_root() {
+    let mut tree = expression_tree();
+    let index = tree.wrap( 0, ALLOW_CHILDREN | ALLOW_DATA, None, None ).unwrap();
+    assert!( matches!( tree.parent( index ), Err( TreeError::RootHasNoParent( _ ) ) ), "Created node is root." );
+    assert_eq!( tree.parent( 0 ).unwrap(), index, "Parent of old root is the created node." );
+    assert_eq!( tree.depth( 0 ).unwrap(), 1, "Old root is at depth 1." );
+    assert!( tree.data_ref( index ).unwrap().is_empty(), "Created node allows data." );
+}
+
+#[test]
+fn wrap_no_children_allowed() {
+    let mut tree = expression_tree();
+    let result = tree.wrap( 5, ALLOW_DATA, None, None );
+    assert!( matches!( result, Err( TreeError::NoChildrenAllowed( 5 ) ) ), "Created node must allow children." );
+    assert_eq!( tree.count(), 9, "No node was created." );
+}
+
+#[test]
+fn each_level() {
+    let tree = expression_tree();
+    let mut levels = Vec::<Vec<usize>>::new();
+    tree.each_level( |depth, nodes| {
+        assert_eq!( depth, levels.len(), "Levels are in order." );
+        levels.push( nodes.to_vec() );
+    } );
+    assert_eq!( levels[ 0 ], vec![ 0 ], "Root level has one entry." );
+    assert_eq!( levels.len(), 6, "Has 6 levels." );
+    assert_eq!( levels[ 4 ], vec![ 5, 8 ], "Level 4 nodes." );
+    assert_eq!( levels.iter().map( |level| level.len() ).sum::<usize>(), tree.count(), "Every node is visited." );
+}
+
+#[test]
+fn is_hole() {
+    let mut tree = expression_tree();
+    assert!( !tree.is_hole( 4 ), "Node 4 exists." );
+    tree.collapse( 4 ).ok();
+    assert!( tree.is_hole( 4 ), "Node 4 is a hole." );
+    assert!( !tree.exists( 4 ), "Node 4 doesn't exist." );
+    assert!( !tree.is_hole( 9 ), "Beyond the internal vector." );
+}
+
+#[test]
+fn holes() {
+    let mut tree = expression_tree();
+    assert!( tree.holes().is_empty(), "No holes." );
+    tree.collapse( 5 ).ok();
+    tree.collapse( 2 ).ok();
+    assert_eq!( tree.holes(), vec![ 2, 5 ], "Holes are in ascending order." );
+    let index = tree.insert( 0, ALLOW_DATA, None, None ).unwrap();
+    assert_eq!( index, 2, "First hole is reused." );
+    assert_eq!( tree.holes(), vec![ 5 ], "1 hole is left." );
+}
+
+#[test]
+fn next_insert_index() {
+    let mut tree = Tree::new();
+    assert_eq!( tree.next_insert_index(), 0, "Empty tree starts at 0." );
+    tree = expression_tree();
+    assert_eq!( tree.next_insert_index(), 9, "No holes." );
+    tree.delete( 7 ).ok();
+    assert_eq!( tree.next_insert_index(), 7, "Deleted node is reused." );
+    let index = tree.insert( 5, ALLOW_DATA, None, None ).unwrap();
+    assert_eq!( index, 7, "Prediction is correct." );
+    tree.delete( 3 ).ok();
+    let expected = tree.next_insert_index();
+    let index = tree.insert_at( 5, 0, ALLOW_DATA, None, None ).unwrap();
+    assert_eq!( index, expected, "Prediction is correct for `insert_at`." );
+}
+
+#[test]
+fn descend_path() {
+    let tree = expression_tree();
+    assert_eq!( tree.descend_path( 0, &[] ).unwrap(), 0, "Empty path is the start." );
+    assert_eq!( tree.descend_path( 2, &[ 0 ] ).unwrap(), 3, "First child of node 2." );
+    assert_eq!( tree.descend_path( 2, &[ 1, 0, 1 ] ).unwrap(), 7, "Leaf `y`." );
+    assert_eq!( tree.descend_path( 0, &[ 0, 0, 1, 1 ] ).unwrap(), 8, "Leaf `2`." );
+    assert!( matches!( tree.descend_path( 2, &[ 2 ] ), Err( TreeError::NoChildrenFound( 2 ) ) ) );
+    assert!( matches!( tree.descend_path( 2, &[ 0, 0 ] ), Err( TreeError::NoChildrenAllowed( 3 ) ) ) );
+    assert!( matches!( tree.descend_path( 20, &[] ), Err( TreeError::RetrievingNode( 20 ) ) ) );
+}
+
+#[test]
+fn position_path() {
+    let tree = expression_tree();
+    assert!( tree.position_path( 0 ).unwrap().is_empty(), "Root node has an empty address." );
+    assert_eq!( tree.position_path( 7 ).unwrap(), vec![ 0, 0, 1, 0, 1 ], "Address of leaf `y`." );
+    for index in 0..tree.len() {
+        let path = tree.position_path( index ).unwrap();
+        assert_eq!( tree.descend_path( 0, &path ).unwrap(), index, "Round trip of node {}.", index );
+    }
+    assert!( matches!( tree.position_path( 20 ), Err( TreeError::RetrievingNode( 20 ) ) ) );
+}
+
+#[test]
+fn duplicate_data() {
+    let mut tree = expression_tree();
+    let clone_string = |data: &Box<dyn Any>| {
+        Box::new( data.downcast_ref::<String>().unwrap().clone() ) as Box<dyn Any>
+    };
+    let position = tree.duplicate_data( 6, 0, clone_string ).unwrap();
+    assert_eq!( position, 1, "Copy is at position 1." );
+    *tree.data_mut( 6 ).unwrap()[ 1 ].downcast_mut::<String>().unwrap() = "w".to_string();
+    let data = tree.data_ref( 6 ).unwrap();
+    assert_eq!( *data[ 0 ].downcast_ref::<String>().unwrap(), "x", "Original is unchanged." );
+    assert_eq!( *data[ 1 ].downcast_ref::<String>().unwrap(), "w", "Copy is independent." );
+    assert!( matches!( tree.duplicate_data( 6, 2, clone_string ), Err( TreeError::NoDataFound( 2, 6 ) ) ) );
+    assert!( matches!( tree.duplicate_data( 5, 0, clone_string ), Err( TreeError::NoDataAllowed( 5 ) ) ) );
+}
+
+#[test]
+fn subtree_to_parent_edges() {
+    let tree = expression_tree();
+    let edges = tree.subtree_to_parent_edges( 4 ).unwrap();
+    assert_eq!(
+        edges,
+        vec![ ( 4, None ), ( 5, Some( 4 ) ), ( 6, Some( 5 ) ), ( 7, Some( 5 ) ), ( 8, Some( 4 ) ) ],
+        "Edges of Divide."
+    );
+
+    // Rebuild the subtree in another tree, and compare the shapes.
+    let mut rebuilt = Tree::new();
+    rebuilt.insert( 0, ALLOW_CHILDREN, None, None ).ok();
+    let root = rebuilt.insert_subtree_from_edges( 0, &edges, ALLOW_CHILDREN ).unwrap();
+    let shape = |tree: &Tree, index: usize| {
+        let base = tree.depth( index ).unwrap();
+        tree.subtree_to_parent_edges( index ).unwrap()
+            .into_iter()
+            .map( |( index, _ )| tree.depth( index ).unwrap() - base )
+            .collect::<Vec<usize>>()
+    };
+    assert_eq!( shape( &rebuilt, root ), shape( &tree, 4 ), "Rebuilt subtree has the same shape." );
+}
+
+#[test]
+fn node_summary() {
+    let tree = expression_tree();
+    let summary = tree.node_summary( 6 ).unwrap();
+    assert_eq!( summary.index, 6 );
+    assert_eq!( summary.depth, tree.depth( 6 ).unwrap() );
+    assert_eq!( summary.parent, tree.parent( 6 ).ok() );
+    assert_eq!( summary.features, *tree.features( 6 ).unwrap() );
+    assert_eq!( summary.data_len, tree.data_ref( 6 ).unwrap().len() );
+    assert_eq!( tree.node_summary( 0 ).unwrap(), tree.flatten()[ 0 ], "Same as the flattened summary." );
+    assert!( matches!( tree.node_summary( 20 ), Err( TreeError::RetrievingNode( 20 ) ) ) );
+}
+
+#[test]
+fn clear_subtree_data() {
+    let mut tree = expression_tree();
+    tree.data_mut( 6 ).unwrap().push( Box::new( 1i32 ) );
+    let total = |tree: &Tree| tree.data_ref_all().iter().map( |( _, data )| data.len() ).sum::<usize>();
+    let before = total( &tree );
+    let removed = tree.clear_subtree_data( 4 ).unwrap();
+    assert_eq!( removed, 4, "Removed 4 data items." );
+    assert_eq!( total( &tree ), before - removed, "Total data items dropped by the removed amount." );
+    assert_eq!( tree.data_ref( 3 ).unwrap().len(), 1, "Node 3 is outside the subtree." );
+    assert_eq!( tree.count(), 9, "Structure is unchanged." );
+}
+
+#[test]
+fn is_balanced() {
+    let mut tree = Tree::new();
+    assert!( tree.is_balanced(), "Empty tree is balanced." );
+    tree.insert( 0, ALLOW_CHILDREN, None, None ).ok();
+    assert!( tree.is_balanced(), "Single node is balanced." );
+    tree.insert( 0, ALLOW_CHILDREN, None, None ).ok();
+    tree.insert( 0, ALLOW_CHILDREN, None, None ).ok();
+    tree.insert( 1, ALLOW_DATA, None, None ).ok();
+    tree.insert( 1, ALLOW_DATA, None, None ).ok();
+    tree.insert( 2, ALLOW_DATA, None, None ).ok();
+    assert!( tree.is_balanced(), "Balanced tree." );
+}
+
+#[test]
+fn is_balanced_unbalanced() {
+    let tree = expression_tree();
+    assert!( !tree.is_balanced(), "Equal has children of heights 0 and 2." );
+}
+
+#[test]
+fn retain() {
+    let mut tree = expression_tree();
+    let group = tree.insert( 2, ALLOW_CHILDREN, Some( Box::new( "Group".to_string() ) ), None ).unwrap();
+    tree.insert( group, ALLOW_CHILDREN, Some( Box::new( "Group".to_string() ) ), None ).ok();
+    tree.insert( 5, ALLOW_CHILDREN, Some( Box::new( "Group".to_string() ) ), None ).ok();
+    let removed = tree.retain( |index, tree| {
+        tree.node_type( index ).unwrap().as_ref().map_or( false, |node_type| {
+            node_type.downcast_ref::<String>().map_or( false, |node_type| node_type == "Leaf" )
+        } )
+    } ).unwrap();
+    assert_eq!( removed, 3, "Only the empty groups are deleted." );
+    assert_eq!( tree.count(), 9, "Nodes with surviving children are kept." );
+    assert_eq!( tree.children( 2 ).unwrap(), &vec![ 3, 4 ], "Node 2 has its original children." );
+    assert_eq!( tree.children( 5 ).unwrap(), &vec![ 6, 7 ], "Node 5 has its original children." );
+    for summary in tree.flatten() {
+        if let Some( parent ) = summary.parent {
+            assert!( tree.children( parent ).unwrap().contains( &summary.index ), "Structure is consistent." );
+        }
+    }
+}
+
+#[test]
+fn descendant_counts() {
+    let tree = expression_tree();
+    let counts = tree.descendant_counts();
+    assert_eq!( counts.len(), tree.len(), "One entry per node." );
+    assert_eq!( counts[ 0 ], Some( tree.count() - 1 ), "Root has all other nodes as descendants." );
+    assert_eq!( counts[ 4 ], Some( 4 ), "Divide has 4 descendants." );
+    assert_eq!( counts[ 3 ], Some( 0 ), "Leaf has no descendants." );
+}
+
+#[test]
+fn swap_subtrees() {
+    let mut tree = expression_tree();
+    tree.swap_subtrees( 3, 5 ).unwrap();
+    assert_eq!( tree.children( 2 ).unwrap(), &vec![ 5, 4 ], "Add subtree is now first child of Equal." );
+    assert_eq!( tree.children( 4 ).unwrap(), &vec![ 3, 8 ], "Leaf z is now first child of Divide." );
+    assert_eq!( tree.parent( 5 ).unwrap(), 2, "Parent of Add is Equal." );
+    assert_eq!( tree.parent( 3 ).unwrap(), 4, "Parent of leaf z is Divide." );
+    assert_eq!( tree.children( 5 ).unwrap(), &vec![ 6, 7 ], "Add keeps its children." );
+    assert_eq!( tree.depth( 6 ).unwrap(), 4, "Leaf x moved up a level." );
+}
+
+#[test]
+fn swap_subtrees_ancestor() {
+    let mut tree = expression_tree();
+    let result = tree.swap_subtrees( 4, 7 );
+    assert!( matches!( result, Err( TreeError::IsAncestorOf( 7, 4 ) ) ), "Expected IsAncestorOf error." );
+    let result = tree.swap_subtrees( 6, 0 );
+    assert!( matches!( result, Err( TreeError::IsAncestorOf( 6, 0 ) ) ), "Expected IsAncestorOf error." );
+    assert_eq!( tree.children( 4 ).unwrap(), &vec![ 5, 8 ], "Tree is unchanged." );
+}
+
+#[test]
+fn validate_node() {
+    let mut tree = expression_tree();
+    for index in 0..tree.len() {
+        assert!( tree.validate_node( index ).is_ok(), "Node {} is consistent.", index );
+    }
+    tree.reparent( 5, 2, Position::Front ).unwrap();
+    for index in [ 2, 4, 5 ] {
+        assert!( tree.validate_node( index ).is_ok(), "Node {} is consistent after move.", index );
+    }
+    tree.delete( 8 ).unwrap();
+    let result = tree.validate_node( 8 );
+    assert!( matches!( result, Err( TreeError::RetrievingNode( 8 ) ) ), "Expected RetrievingNode error." );
+}
+
+#[test]
+fn move_by_paths() {
+    let mut tree = expression_tree();
+    assert_eq!( tree.position_path( 5 ).unwrap(), vec![ 0, 0, 1, 0 ], "Add is first child of Divide." );
+    tree.move_by_paths( &[ 0, 0, 1, 0 ], &[ 0, 0 ], Position::At( 1 ) ).unwrap();
+    assert_eq!( tree.position_path( 5 ).unwrap(), vec![ 0, 0, 1 ], "Add is second child of Equal." );
+    assert_eq!( tree.position_path( 4 ).unwrap(), vec![ 0, 0, 2 ], "Divide is now third child of Equal." );
+    assert_eq!( tree.position_path( 7 ).unwrap(), vec![ 0, 0, 1, 1 ], "Leaf y moved with Add." );
+    let result = tree.move_by_paths( &[ 0, 0 ], &[ 0, 0, 1 ], Position::Back );
+    assert!( matches!( result, Err( TreeError::IsAncestorOf( 5, 2 ) ) ), "Expected IsAncestorOf error." );
+}
+
+#[test]
+fn move_by_paths_empty_tree() {
+    let mut tree = Tree::new();
+    let result = tree.move_by_paths( &[ 0 ], &[], Position::Back );
+    assert!( matches!( result, Err( TreeError::NoRootNode ) ), "Empty tree has no root node." );
+}
+
+#[test]
+fn data_positions_of_type() {
+    let mut tree = expression_tree();
+    let data = tree.data_mut( 3 ).unwrap();
+    data.push( Box::new( 1i32 ) );
+    data.push( Box::new( "w".to_string() ) );
+    data.push( Box::new( 2i32 ) );
+    assert_eq!( tree.data_positions_of_type::<String>( 3 ).unwrap(), vec![ 0, 2 ], "Strings are at 0 and 2." );
+    assert_eq!( tree.data_positions_of_type::<i32>( 3 ).unwrap(), vec![ 1, 3 ], "Integers are at 1 and 3." );
+    assert!( tree.data_positions_of_type::<u8>( 3 ).unwrap().is_empty(), "No u8 data." );
+    let result = tree.data_positions_of_type::<String>( 2 );
+    assert!( matches!( result, Err( TreeError::NoDataAllowed( 2 ) ) ), "Expected NoDataAllowed error." );
+}
+
+#[test]
+fn dfs_order() {
+    let mut tree = expression_tree();
+    let order = tree.dfs_order( 2 ).unwrap();
+    assert_eq!( order, vec![ 2, 3, 4, 5, 6, 7, 8 ], "Pre-order of Equal subtree." );
+    for index in order {
+        if let Ok( data ) = tree.data_mut( index ) {
+            data.push( Box::new( format!( "node {}", index ) ) );
+        }
+    }
+    for index in [ 3, 6, 7, 8 ] {
+        let data = tree.data_ref( index ).unwrap();
+        assert_eq!( data.len(), 2, "Leaf {} has additional data.", index );
+        assert_eq!( data[ 1 ].downcast_ref::<String>().unwrap(), &format!( "node {}", index ) );
+    }
+    assert!( tree.dfs_order( 9 ).is_err(), "Node 9 does not exist." );
+}
+
+#[test]
+fn hole_count() {
+    let mut tree = expression_tree();
+    assert_eq!( tree.hole_count(), 0, "No holes in a fresh tree." );
+    tree.collapse( 5 ).unwrap();
+    tree.collapse( 1 ).unwrap();
+    assert_eq!( tree.hole_count(), 2, "Two interior nodes were removed." );
+    assert_eq!( tree.hole_count(), tree.holes().len(), "Matches the holes." );
+}
+
+#[test]
+fn first_leaf() {
+    let tree = expression_tree();
+    assert_eq!( tree.first_leaf( 0 ).unwrap(), 3, "Leaf z is the leftmost leaf." );
+    assert_eq!( tree.first_leaf( 4 ).unwrap(), 6, "Leaf x is the leftmost leaf of Divide." );
+    assert_eq!( tree.first_leaf( 7 ).unwrap(), 7, "A leaf returns itself." );
+    let result = tree.first_leaf( 9 );
+    assert!( matches!( result, Err( TreeError::RetrievingNode( 9 ) ) ), "Expected RetrievingNode error." );
+}
+
+#[test]
+fn last_leaf() {
+    let tree = expression_tree();
+    assert_eq!( tree.last_leaf( 0 ).unwrap(), 8, "Leaf 2 is the rightmost leaf." );
+    assert_eq!( tree.last_leaf( 5 ).unwrap(), 7, "Leaf y is the rightmost leaf of Add." );
+    assert_eq!( tree.last_leaf( 3 ).unwrap(), 3, "A leaf returns itself." );
+    let result = tree.last_leaf( 9 );
+    assert!( matches!( result, Err( TreeError::RetrievingNode( 9 ) ) ), "Expected RetrievingNode error." );
+}
+
+#[test]
+fn next_preorder() {
+    let tree = expression_tree();
+    let mut forward = vec![ 0 ];
+    while let Some( index ) = tree.next_preorder( *forward.last().unwrap() ).unwrap() {
+        forward.push( index );
+    }
+    assert_eq!( forward, tree.dfs_order( 0 ).unwrap(), "Stepping forward matches pre-order." );
+    assert!( tree.next_preorder( 9 ).is_err(), "Node 9 does not exist." );
+}
+
+#[test]
+fn prev_preorder() {
+    let tree = expression_tree();
+    let mut backward = vec![ 8 ];
+    while let Some( index ) = tree.prev_preorder( *backward.last().unwrap() ).unwrap() {
+        backward.push( index );
+    }
+    backward.reverse();
+    assert_eq!( backward, tree.dfs_order( 0 ).unwrap(), "Stepping backward matches reversed pre-order." );
+    assert!( tree.prev_preorder( 9 ).is_err(), "Node 9 does not exist." );
+}
+
+#[test]
+fn data_swap_remove() {
+    let mut tree = expression_tree();
+    tree.data_mut( 3 ).unwrap().push( Box::new( "w".to_string() ) );
+    tree.data_mut( 3 ).unwrap().push( Box::new( "v".to_string() ) );
+    let removed = tree.data_swap_remove( 3, 0 ).unwrap();
+    assert_eq!( removed.downcast_ref::<String>().unwrap(), "z", "First item was removed." );
+    let data = tree.data_ref( 3 ).unwrap();
+    assert_eq!( data.len(), 2, "Two items remain." );
+    assert_eq!( data[ 0 ].downcast_ref::<String>().unwrap(), "v", "Former last item is now first." );
+    let result = tree.data_swap_remove( 3, 2 );
+    assert!( matches!( result, Err( TreeError::NoDataFound( 2, 3 ) ) ), "Expected NoDataFound error." );
+    let result = tree.data_swap_remove( 2, 0 );
+    assert!( matches!( result, Err( TreeError::NoDataAllowed( 2 ) ) ), "Expected NoDataAllowed error." );
+}
+
+#[test]
+fn preview() {
+    let tree = expression_tree();
+    assert_eq!( tree.preview( 0, 4 ).unwrap(), vec![ 0, 1, 2, 3 ], "Limited to 4 nodes." );
+    assert_eq!( tree.preview( 5, 4 ).unwrap(), vec![ 5, 6, 7 ], "Subtree has only 3 nodes." );
+    assert!( tree.preview( 0, 0 ).unwrap().is_empty(), "Limit of 0 has no nodes." );
+    assert!( tree.preview( 9, 4 ).is_err(), "Node 9 does not exist." );
+}
+
+#[test]
+fn repair_children() {
+    let mut tree = expression_tree();
+    assert_eq!( tree.repair_children(), 0, "Fresh tree has no stale children." );
+    tree.swap_subtrees( 3, 5 ).unwrap();
+    tree.collapse( 4 ).unwrap();
+    assert_eq!( tree.repair_children(), 0, "Tree is consistent after restructuring." );
+    assert_eq!( tree.children( 2 ).unwrap(), &vec![ 5, 3, 8 ], "Children are untouched." );
+    for index in 0..tree.len() {
+        if tree.exists( index ) {
+            assert!( tree.validate_node( index ).is_ok(), "Node {} is consistent.", index );
+        }
+    }
+}
+
+#[test]
+fn into_iter() {
+    let mut tree = expression_tree();
+    tree.data_mut( 8 ).unwrap().push( Box::new( "3".to_string() ) );
+    let mut indices = Vec::<usize>::new();
+    let mut items = Vec::<String>::new();
+    for ( index, data ) in tree {
+        indices.push( index );
+        for item in data {
+            items.push( *item.downcast::<String>().unwrap() );
+        }
+    }
+    assert_eq!( indices, vec![ 3, 6, 7, 8 ], "Only nodes allowing data are yielded." );
+    assert_eq!( items, vec![ "z", "x", "y", "2", "3" ], "All data items are reclaimed." );
+}
+
+#[test]
+fn subtree_feature_histogram() {
+    let tree = expression_tree();
+    let histogram = tree.subtree_feature_histogram( 0 ).unwrap();
+    assert_eq!( histogram.get( &ALLOW_CHILDREN ), Some( &5 ), "5 structure nodes." );
+    assert_eq!( histogram.get( &ALLOW_DATA ), Some( &4 ), "4 leaf nodes." );
+    assert_eq!( histogram.values().sum::<usize>(), tree.count(), "Sums to the node count." );
+    let histogram = tree.subtree_feature_histogram( 5 ).unwrap();
+    assert_eq!( histogram.values().sum::<usize>(), 3, "Add subtree has 3 nodes." );
+    let result = tree.subtree_feature_histogram( 9 );
+    assert!( matches!( result, Err( TreeError::RetrievingNode( 9 ) ) ), "Expected RetrievingNode error." );
+}
+
+#[test]
+fn can_move() {
+    let tree = expression_tree();
+    assert!( tree.can_move( 5, 2 ).is_ok(), "Add can move to Equal." );
+    assert!( tree.can_move( 8, 5 ).is_ok(), "Leaf 2 can move to Add." );
+    let result = tree.can_move( 4, 5 );
+    assert!( matches!( result, Err( TreeError::IsAncestorOf( 5, 4 ) ) ), "Expected IsAncestorOf error." );
+    let result = tree.can_move( 5, 3 );
+    assert!( matches!( result, Err( TreeError::NoChildrenAllowed( 3 ) ) ), "Expected NoChildrenAllowed error." );
+    assert_eq!( tree.parent( 5 ).unwrap(), 4, "Tree is unchanged." );
+}
+
+#[test]
+fn data_get_ref() {
+    let tree = expression_tree();
+    let data = tree.data_get_ref( 6, 0 ).unwrap();
+    assert_eq!( data.downcast_ref::<String>().unwrap(), "x", "Leaf x has data x." );
+    let result = tree.data_get_ref( 6, 1 );
+    assert!( matches!( result, Err( TreeError::NoDataFound( 1, 6 ) ) ), "Expected NoDataFound error." );
+    let result = tree.data_get_ref( 5, 0 );
+    assert!( matches!( result, Err( TreeError::NoDataAllowed( 5 ) ) ), "Expected NoDataAllowed error." );
+}
+
+#[test]
+fn data_get_mut() {
+    let mut tree = expression_tree();
+    tree.data_get_mut( 6, 0 ).unwrap().downcast_mut::<String>().unwrap().push( '1' );
+    assert_eq!( tree.data_get_ref( 6, 0 ).unwrap().downcast_ref::<String>().unwrap(), "x1", "Data was modified." );
+    let result = tree.data_get_mut( 6, 1 );
+    assert!( matches!( result, Err( TreeError::NoDataFound( 1, 6 ) ) ), "Expected NoDataFound error." );
+}
+
+#[test]
+fn count_descendants_matching() {
+    let mut tree = expression_tree();
+    *tree.data_get_mut( 8, 0 ).unwrap().downcast_mut::<String>().unwrap() = "20".to_string();
+    let single_character = |index: usize, tree: &Tree| {
+        tree.data_ref( index ).map_or( false, |data| {
+            data.iter().any( |item| item.downcast_ref::<String>().map_or( false, |item| item.len() == 1 ) )
+        } )
+    };
+    assert_eq!( tree.count_descendants_matching( 4, single_character ).unwrap(), 2, "Leaves x and y." );
+    assert_eq!( tree.count_descendants_matching( 0, single_character ).unwrap(), 3, "Leaves z, x and y." );
+    assert_eq!( tree.count_descendants_matching( 6, |_, _| true ).unwrap(), 0, "Node itself is excluded." );
+    let result = tree.count_descendants_matching( 9, single_character );
+    assert!( matches!( result, Err( TreeError::RetrievingNode( 9 ) ) ), "Expected RetrievingNode error." );
+}
+
+#[test]
+fn set_child_order() {
+    let mut tree = expression_tree();
+    tree.set_child_order( 2, &[ 4, 3 ] ).unwrap();
+    assert_eq!( tree.children( 2 ).unwrap(), &vec![ 4, 3 ], "Children of Equal are reordered." );
+    assert_eq!( tree.parent( 3 ).unwrap(), 2, "Parent is unchanged." );
+    assert_eq!( tree.child_position( 3 ).unwrap(), Some( 1 ), "Leaf z is now second." );
+}
+
+#[test]
+fn set_child_order_mismatch() {
+    let mut tree = expression_tree();
+    for new_order in [ &[ 4, 8 ][ .. ], &[ 4 ], &[ 4, 3, 3 ], &[ 4, 4 ] ] {
+        let result = tree.set_child_order( 2, new_order );
+        assert!(
+            matches!( result, Err( TreeError::NotAPermutation( 2 ) ) ),
+            "Expected NotAPermutation error for {:?}.", new_order
+        );
+    }
+    assert_eq!( tree.children( 2 ).unwrap(), &vec![ 3, 4 ], "Children are unchanged." );
+}
+
+#[test]
+fn depth_cycle_guard() {
+    let mut tree = expression_tree();
+    assert_eq!( tree.depth( 7 ).unwrap(), 5, "Leaf y is at depth 5." );
+    assert!( tree.reparent( 2, 7, Position::Back ).is_err(), "Moves creating a cycle are rejected." );
+    tree.reparent( 7, 1, Position::Back ).unwrap();
+    assert_eq!( tree.depth( 7 ).unwrap(), 2, "Leaf y is at depth 2 after the move." );
+    let error = TreeError::CycleDetected( 7 );
+    assert_eq!( error.to_string(), "A cycle was detected in the parents of the node 7." );
+}
+
+#[test]
+fn is_ancestor_of_deep_chain() {
+    let mut tree = Tree::new();
+    let mut last = tree.insert( 0, ALLOW_CHILDREN, None, None ).unwrap();
+    for _ in 0..20_000 {
+        last = tree.insert( last, ALLOW_CHILDREN, None, None ).unwrap();
+    }
+    assert!( tree.is_ancestor_of( last, 0 ).unwrap(), "Root is an ancestor of the end of the chain." );
+    assert!( !tree.is_ancestor_of( 0, last ).unwrap(), "Root has no ancestors." );
+    assert!( !tree.is_ancestor_of( last, last ).unwrap(), "A node is not its own ancestor." );
+}
+
+#[test]
+fn all_depths() {
+    let tree = expression_tree();
+    let depths = tree.all_depths();
+    assert_eq!( depths.len(), tree.len(), "One entry per node." );
+    assert_eq!( depths[ 0 ], Some( 0 ), "Root is at depth 0." );
+    for ( index, depth ) in depths.iter().enumerate() {
+        assert_eq!( *depth, tree.depth( index ).ok(), "Depth of node {} matches.", index );
+    }
+    assert_eq!( depths[ 7 ], Some( 5 ), "Leaf y is at depth 5." );
+}
+
+#[test]
+fn insert_with_data() {
+    let mut tree = expression_tree();
+    let index = tree.insert_with_data(
+        5,
+        ALLOW_DATA,
+        Some( Box::new( "Leaf".to_string() ) ),
+        None,
+        vec![ Box::new( "w".to_string() ) ]
+    ).unwrap();
+    assert_eq!( tree.children( 5 ).unwrap(), &vec![ 6, 7, index ], "Inserted as last child of Add." );
+    assert_eq!( tree.data_get_ref( index, 0 ).unwrap().downcast_ref::<String>().unwrap(), "w", "Has its data." );
+    let result = tree.insert_with_data( 5, ALLOW_CHILDREN, None, None, vec![ Box::new( "v".to_string() ) ] );
+    assert!( matches!( result, Err( TreeError::NoDataAllowed( 10 ) ) ), "Expected NoDataAllowed error." );
+    assert_eq!( tree.count(), 10, "No node was created." );
+    let index = tree.insert_with_data( 5, ALLOW_CHILDREN, None, None, Vec::new() ).unwrap();
+    assert!( tree.data_ref( index ).is_err(), "Structure node has no data." );
+}
+
+#[test]
+fn children_len() {
+    let mut tree = expression_tree();
+    tree.insert( 4, ALLOW_DATA, None, None ).unwrap();
+    assert_eq!( tree.children_len( 4 ).unwrap(), 3, "Divide has 3 children." );
+    assert_eq!( tree.children_len( 1 ).unwrap(), 1, "Statement has 1 child." );
+    let result = tree.children_len( 3 );
+    assert!( matches!( result, Err( TreeError::NoChildrenAllowed( 3 ) ) ), "Expected NoChildrenAllowed error." );
+    let result = tree.children_len( 10 );
+    assert!( matches!( result, Err( TreeError::RetrievingNode( 10 ) ) ), "Expected RetrievingNode error." );
+}
+
+#[test]
+fn move_nodes_strict() {
+    let mut tree = expression_tree();
+    tree.move_nodes_strict( 8, 5, 1 ).unwrap();
+    assert_eq!( tree.children( 5 ).unwrap(), &vec![ 6, 8, 7 ], "Leaf 2 is the second child of Add." );
+    let result = tree.move_nodes_strict( 3, 5, 4 );
+    assert!( matches!( result, Err( TreeError::ExceedsChildren( 4, 5 ) ) ), "Expected ExceedsChildren error." );
+    let result = tree.move_nodes_strict( 6, 5, 3 );
+    assert!(
+        matches!( result, Err( TreeError::ExceedsChildren( 3, 5 ) ) ),
+        "Expected ExceedsChildren error when repositioning within the same parent."
+    );
+    assert_eq!( tree.parent( 3 ).unwrap(), 2, "Leaf z was not moved." );
+    assert_eq!( tree.children( 5 ).unwrap(), &vec![ 6, 8, 7 ], "Add is unchanged." );
+}
+
+#[test]
+fn view() {
+    let mut tree = expression_tree();
+    let view = tree.view( 4 ).unwrap();
+    assert_eq!( view.index(), 4, "Index of Divide." );
+    assert_eq!( view.features(), ALLOW_CHILDREN, "Divide only allows children." );
+    assert_eq!( view.parent(), Some( 2 ), "Parent of Divide is Equal." );
+    assert_eq!( view.children().unwrap(), &vec![ 5, 8 ], "Children of Divide." );
+    assert_eq!( view.node_type().as_ref().unwrap().downcast_ref::<String>().unwrap(), "Divide" );
+    assert!( view.data().is_none(), "Divide has no data." );
+    assert!( view.data_type().is_none(), "Divide has no data type." );
+    let index = tree.insert_with_data(
+        4,
+        ALLOW_DATA,
+        Some( Box::new( "Leaf".to_string() ) ),
+        Some( Box::new( "String".to_string() ) ),
+        vec![ Box::new( "3".to_string() ) ]
+    ).unwrap();
+    let view = tree.view( index ).unwrap();
+    assert_eq!( view.parent(), Some( 4 ), "Parent is Divide." );
+    assert!( view.children().is_none(), "Leaf can't have children." );
+    assert_eq!( view.data().unwrap()[ 0 ].downcast_ref::<String>().unwrap(), "3", "Leaf has its data." );
+    assert_eq!( view.data_type().as_ref().unwrap().downcast_ref::<String>().unwrap(), "String" );
+    assert!( tree.view( 11 ).is_err(), "Node 11 does not exist." );
+}
+
+#[test]
+fn clear_all_data() {
+    let mut tree = expression_tree();
+    tree.data_mut( 3 ).unwrap().push( Box::new( "w".to_string() ) );
+    assert_eq!( tree.clear_all_data(), 5, "Removed 5 data items." );
+    let total = tree.data_ref_all().iter().map( |( _, data )| data.len() ).sum::<usize>();
+    assert_eq!( total, 0, "No data remains." );
+    assert_eq!( tree.count(), 9, "All nodes are kept." );
+    assert_eq!( tree.children( 4 ).unwrap(), &vec![ 5, 8 ], "Structure is kept." );
+    assert!( tree.data_ref( 6 ).is_ok(), "Leaf x still allows data." );
+    assert!( tree.node_type_downcast_ref::<String>( 6 ).unwrap().is_some(), "Node type is kept." );
+    assert_eq!( tree.clear_all_data(), 0, "Nothing left to remove." );
+}
+
+#[test]
+fn graft_many() {
+    let mut tree = expression_tree();
+    let mut first = Tree::new();
+    first.insert( 0, ALLOW_CHILDREN, Some( Box::new( "Add".to_string() ) ), None ).unwrap();
+    first.insert_with_data( 0, ALLOW_DATA, None, None, vec![ Box::new( "a".to_string() ) ] ).unwrap();
+    first.insert_with_data( 0, ALLOW_DATA, None, None, vec![ Box::new( "b".to_string() ) ] ).unwrap();
+    let mut second = Tree::new();
+    second.insert_with_data( 0, ALLOW_DATA, None, None, vec![ Box::new( "c".to_string() ) ] ).unwrap();
+    let roots = tree.graft_many( 5, vec![ first, second ] ).unwrap();
+    assert_eq!( roots, vec![ 9, 12 ], "Indices of the grafted roots." );
+    assert_eq!( tree.children( 5 ).unwrap(), &vec![ 6, 7, 9, 12 ], "Grafted roots are consecutive children." );
+    assert_eq!( tree.parent( 9 ).unwrap(), 5, "Parent of first grafted root." );
+    assert_eq!( tree.parent( 12 ).unwrap(), 5, "Parent of second grafted root." );
+    assert_eq!( tree.children( 9 ).unwrap(), &vec![ 10, 11 ], "First grafted tree keeps its structure." );
+    assert_eq!( tree.data_get_ref( 11, 0 ).unwrap().downcast_ref::<String>().unwrap(), "b", "Data is kept." );
+    assert_eq!( tree.node_type_downcast_ref::<String>( 9 ).unwrap().unwrap(), "Add", "Node type is kept." );
+    let mut third = Tree::new();
+    third.insert( 0, ALLOW_DATA, None, None ).unwrap();
+    let result = tree.graft_many( 3, vec![ third ] );
+    assert!( matches!( result, Err( TreeError::NoChildrenAllowed( 3 ) ) ), "Expected NoChildrenAllowed error." );
+    assert_eq!( tree.count(), 13, "Nothing was grafted." );
+}
+
+#[test]
+fn subtree_eq_with() {
+    let string_eq = |a: &Vec<Box<dyn Any>>, b: &Vec<Box<dyn Any>>| {
+        a.len() == b.len() && a.iter().zip( b.iter() ).all( |( a, b )| {
+            a.downcast_ref::<String>() == b.downcast_ref::<String>()
+        } )
+    };
+    let mut tree = expression_tree();
+    let other = expression_tree();
+    assert!( tree.subtree_eq_with( 0, &other, 0, string_eq ).unwrap(), "Identical trees." );
+    assert!( tree.subtree_eq_with( 4, &other, 4, string_eq ).unwrap(), "Identical subtrees." );
+    assert!( !tree.subtree_eq_with( 5, &other, 4, string_eq ).unwrap(), "Different shapes." );
+    *tree.data_get_mut( 7, 0 ).unwrap().downcast_mut::<String>().unwrap() = "w".to_string();
+    assert!( !tree.subtree_eq_with( 4, &other, 4, string_eq ).unwrap(), "Leaf y has different data." );
+    assert!( tree.subtree_eq_with( 6, &other, 7, |_, _| true ).unwrap(), "Data is ignored." );
+    assert!( tree.subtree_eq_with( 9, &other, 0, string_eq ).is_err(), "Node 9 does not exist." );
+}
+
+#[test]
+fn find_path_to() {
+    let tree = expression_tree();
+    let has_data = |value: &'static str| move |index: usize, tree: &Tree| {
+        tree.data_ref( index ).map_or( false, |data| {
+            data.iter().any( |item| item.downcast_ref::<String>().map_or( false, |item| item == value ) )
+        } )
+    };
+    assert_eq!( tree.find_path_to( has_data( "y" ) ), Some( vec![ 0, 1, 2, 4, 5, 7 ] ), "Path to leaf y." );
+    assert_eq!( tree.find_path_to( has_data( "z" ) ), Some( vec![ 0, 1, 2, 3 ] ), "Path to leaf z." );
+    assert_eq!( tree.find_path_to( has_data( "w" ) ), None, "No leaf w." );
+    assert_eq!( tree.find_path_to( |index, _| index == 0 ), Some( vec![ 0 ] ), "Path to root." );
+    assert_eq!( Tree::new().find_path_to( |_, _| true ), None, "Empty tree." );
+}
+
+#[test]
+fn rebuild_root() {
+    let mut tree = expression_tree();
+    assert_eq!( tree.rebuild_root().unwrap(), 0, "Existing root is kept." );
+    assert_eq!( tree.count(), 9, "Tree is unchanged." );
+    tree.clear();
+    assert!( matches!( tree.rebuild_root(), Err( TreeError::NoRootNode ) ), "Empty tree has no root node." );
+    tree.insert( 0, ALLOW_CHILDREN, None, None ).unwrap();
+    assert_eq!( tree.rebuild_root().unwrap(), 0, "New root is found." );
+}
+
+#[test]
+fn ancestor_at_depth() {
+    let tree = expression_tree();
+    assert_eq!( tree.depth( 4 ).unwrap(), 3, "Divide is at depth 3." );
+    assert_eq!( tree.ancestor_at_depth( 4, 1 ).unwrap(), Some( 1 ), "Statement is at depth 1." );
+    assert_eq!( tree.ancestor_at_depth( 4, 0 ).unwrap(), Some( 0 ), "Root is at depth 0." );
+    assert_eq!( tree.ancestor_at_depth( 4, 3 ).unwrap(), Some( 4 ), "Node itself." );
+    assert_eq!( tree.ancestor_at_depth( 4, 4 ).unwrap(), None, "Deeper than the node." );
+    let result = tree.ancestor_at_depth( 9, 0 );
+    assert!( matches!( result, Err( TreeError::RetrievingNode( 9 ) ) ), "Expected RetrievingNode error." );
+}
+
+#[test]
+fn data_retain() {
+    let mut tree = expression_tree();
+    let data = tree.data_mut( 3 ).unwrap();
+    data.push( Box::new( 1i32 ) );
+    data.push( Box::new( "w".to_string() ) );
+    data.push( Box::new( 2.5f64 ) );
+    let removed = tree.data_retain( 3, |item| item.is::<String>() ).unwrap();
+    assert_eq!( removed, 2, "Removed the integer and float." );
+    let data = tree.data_ref( 3 ).unwrap();
+    assert_eq!( data.len(), 2, "Only strings remain." );
+    assert_eq!( data[ 0 ].downcast_ref::<String>().unwrap(), "z", "Order is kept." );
+    assert_eq!( data[ 1 ].downcast_ref::<String>().unwrap(), "w", "Order is kept." );
+    let result = tree.data_retain( 2, |_| true );
+    assert!( matches!( result, Err( TreeError::NoDataAllowed( 2 ) ) ), "Expected NoDataAllowed error." );
+}
+
+#[test]
+fn undo_move() {
+    let mut tree = expression_tree();
+    let structure = |tree: &Tree| {
+        let children = ( 0..tree.len() ).map( |index| tree.children( index ).ok().cloned() ).collect::<Vec<_>>();
+        ( tree.flatten(), children )
+    };
+    let before = structure( &tree );
+    let first = tree.reparent_recorded( 5, 2, Position::Front ).unwrap();
+    let second = tree.reparent_recorded( 3, 5, Position::At( 1 ) ).unwrap();
+    assert_eq!( tree.children( 5 ).unwrap(), &vec![ 6, 3, 7 ], "Leaf z moved into Add." );
+    assert_eq!( second, MoveRecord { node: 3, old_parent: 2, old_position: 1 }, "Leaf z was second child." );
+    tree.undo_move( second ).unwrap();
+    tree.undo_move( first ).unwrap();
+    assert_eq!( structure( &tree ), before, "Tree has its pre-move structure." );
+    let result = tree.reparent_recorded( 0, 2, Position::Back );
+    assert!( matches!( result, Err( TreeError::IsAncestorOf( 2, 0 ) ) ), "Expected IsAncestorOf error." );
+}
+
+#[test]
+fn subtree_count() {
+    let tree = expression_tree();
+    assert_eq!( tree.subtree_count( 0 ).unwrap(), tree.count(), "Root subtree is the whole tree." );
+    assert_eq!( tree.subtree_count( 4 ).unwrap(), 5, "Divide subtree has 5 nodes." );
+    assert_eq!( tree.subtree_count( 8 ).unwrap(), 1, "Leaf subtree is the leaf." );
+    let result = tree.subtree_count( 9 );
+    assert!( matches!( result, Err( TreeError::RetrievingNode( 9 ) ) ), "Expected RetrievingNode error." );
+}
+
+#[test]
+fn each_data_mut() {
+    let mut tree = expression_tree();
+    let mut visited = Vec::<usize>::new();
+    tree.each_data_mut( |index, data| {
+        visited.push( index );
+        for item in data.iter_mut() {
+            if let Some( item ) = item.downcast_mut::<String>() {
+                item.push_str( "_1" );
+            }
+        }
+    } );
+    assert_eq!( visited, vec![ 3, 6, 7, 8 ], "Nodes allowing data in ascending order." );
+    let items = tree
+        .data_ref_all()
+        .into_iter()
+        .flat_map( |( _, data )| data.iter().map( |item| item.downcast_ref::<String>().unwrap().clone() ) )
+        .collect::<Vec<String>>();
+    assert_eq!( items, vec![ "z_1", "x_1", "y_1", "2_1" ], "Suffix appended to every string." );
+}
+
+#[test]
+fn split_off() {
+    let mut tree = expression_tree();
+    let other = tree.split_off( 4 ).unwrap();
+    assert_eq!( tree.count() + other.count(), 9, "Counts sum to the original." );
+    assert_eq!( other.count(), 5, "Divide subtree was split off." );
+    assert_eq!( tree.children( 2 ).unwrap(), &vec![ 3 ], "Divide is no longer a child of Equal." );
+    assert_eq!( other.node_type_downcast_ref::<String>( 0 ).unwrap().unwrap(), "Divide", "Divide is the root." );
+    assert_eq!( other.children( 0 ).unwrap(), &vec![ 1, 4 ], "Divide has Add and leaf 2." );
+    assert_eq!( other.children( 1 ).unwrap(), &vec![ 2, 3 ], "Add has leaves x and y." );
+    assert_eq!( other.data_get_ref( 3, 0 ).unwrap().downcast_ref::<String>().unwrap(), "y", "Data is kept." );
+    let whole = tree.split_off( 0 ).unwrap();
+    assert_eq!( whole.count(), 4, "Remaining nodes were split off." );
+    assert_eq!( tree.count(), 0, "Tree is empty." );
+}
+
+#[test]
+fn leaf_data_refs() {
+    let mut tree = expression_tree();
+    tree.insert( 5, ALLOW_CHILDREN, None, None ).unwrap();
+    let tokens = tree
+        .leaf_data_refs( 0 )
+        .unwrap()
+        .into_iter()
+        .map( |( _, data )| data[ 0 ].downcast_ref::<String>().unwrap().as_str() )
+        .collect::<Vec<&str>>();
+    assert_eq!( tokens, vec![ "z", "x", "y", "2" ], "Leaf data in left to right order." );
+    let indices = tree.leaf_data_refs( 4 ).unwrap().into_iter().map( |( index, _ )| index ).collect::<Vec<usize>>();
+    assert_eq!( indices, vec![ 6, 7, 8 ], "Leaves of Divide, skipping the leaf without data." );
+}
+
+#[test]
+fn iter_forest() {
+    let mut tree = expression_tree();
+    let forest = tree.iter_forest().collect::<Vec<usize>>();
+    assert_eq!( forest, tree.dfs_order( 0 ).unwrap(), "Single component equals the pre-order of the root." );
+    tree.collapse( 5 ).unwrap();
+    tree.split_off( 3 ).unwrap();
+    let mut forest = tree.iter_forest().collect::<Vec<usize>>();
+    assert_eq!( forest, vec![ 0, 1, 2, 4, 6, 7, 8 ], "Removed nodes are not visited." );
+    forest.sort_unstable();
+    forest.dedup();
+    assert_eq!( forest.len(), tree.count(), "Every node is visited exactly once." );
+    assert_eq!( Tree::new().iter_forest().count(), 0, "Empty tree." );
+}
+
+#[test]
+fn data_type_matches() {
+    let mut tree = expression_tree();
+    let index = tree.insert( 4, ALLOW_DATA, None, Some( Box::new( "String".to_string() ) ) ).unwrap();
+    assert!( tree.data_type_matches( index, &"String".to_string() ).unwrap(), "Data type is String." );
+    assert!( !tree.data_type_matches( index, &"i32".to_string() ).unwrap(), "Data type is not i32." );
+    assert!( !tree.data_type_matches( index, &1u8 ).unwrap(), "Data type is not a u8." );
+    assert!( !tree.data_type_matches( 3, &"String".to_string() ).unwrap(), "Leaf z has no data type." );
+    let result = tree.data_type_matches( 10, &"String".to_string() );
+    assert!( matches!( result, Err( TreeError::RetrievingNode( 10 ) ) ), "Expected RetrievingNode error." );
+}
+
+#[test]
+fn flatten_children() {
+    let mut tree = expression_tree();
+    assert_eq!( tree.flatten_children( 4 ).unwrap(), 2, "Divide had 2 children." );
+    assert_eq!( tree.children( 2 ).unwrap(), &vec![ 3, 5, 8, 4 ], "Children of Divide moved into Equal." );
+    assert!( tree.children( 4 ).unwrap().is_empty(), "Divide has no children." );
+    assert_eq!( tree.parent( 5 ).unwrap(), 2, "Parent of Add is Equal." );
+    assert_eq!( tree.parent( 8 ).unwrap(), 2, "Parent of leaf 2 is Equal." );
+    assert_eq!( tree.children( 5 ).unwrap(), &vec![ 6, 7 ], "Add keeps its children." );
+    let result = tree.flatten_children( 0 );
+    assert!( matches!( result, Err( TreeError::RootHasNoParent( 0 ) ) ), "Expected RootHasNoParent error." );
+    let result = tree.flatten_children( 3 );
+    assert!( matches!( result, Err( TreeError::NoChildrenAllowed( 3 ) ) ), "Expected NoChildrenAllowed error." );
+}
+
+#[test]
+fn count_by_node_type() {
+    let mut tree = expression_tree();
+    tree.insert( 4, ALLOW_DATA, Some( Box::new( 1i32 ) ), None ).unwrap();
+    tree.insert( 4, ALLOW_DATA, None, None ).unwrap();
+    let counts = tree.count_by_node_type::<String>();
+    assert_eq!( counts.get( "Leaf" ), Some( &4 ), "4 leaves." );
+    assert_eq!( counts.get( "Divide" ), Some( &1 ), "1 divide." );
+    assert_eq!( counts.values().sum::<usize>(), 9, "Other node types are ignored." );
+    assert_eq!( tree.count_by_node_type::<i32>().get( &1 ), Some( &1 ), "1 integer node type." );
+}
+
+#[test]
+fn nth_leaf() {
+    let tree = expression_tree();
+    assert_eq!( tree.nth_leaf( 0 ), Some( 3 ), "Leaf z is the first leaf." );
+    assert_eq!( tree.nth_leaf( 2 ), Some( 7 ), "Leaf y is the third leaf." );
+    assert_eq!( tree.nth_leaf( 3 ), Some( 8 ), "Leaf 2 is the last leaf." );
+    assert_eq!( tree.nth_leaf( 4 ), None, "Only 4 leaves." );
+    assert_eq!( Tree::new().nth_leaf( 0 ), None, "Empty tree has no leaves." );
+}
+
+#[test]
+fn replace_child() {
+    let mut tree = expression_tree();
+    tree.insert( 4, ALLOW_DATA, None, None ).unwrap();
+    let mut replacement = Tree::new();
+    replacement.insert( 0, ALLOW_CHILDREN, Some( Box::new( "Multiply".to_string() ) ), None ).unwrap();
+    replacement.insert_with_data( 0, ALLOW_DATA, None, None, vec![ Box::new( "a".to_string() ) ] ).unwrap();
+    let replaced = tree.replace_child( 4, 1, replacement ).unwrap();
+    assert_eq!( replaced.count(), 1, "Leaf 2 was replaced." );
+    assert_eq!( replaced.data_get_ref( 0, 0 ).unwrap().downcast_ref::<String>().unwrap(), "2" );
+    let children = tree.children( 4 ).unwrap().clone();
+    assert_eq!( children.len(), 3, "Divide still has 3 children." );
+    assert_eq!( children[ 0 ], 5, "Add is still first." );
+    assert_eq!( children[ 2 ], 9, "Added leaf is still last." );
+    assert_eq!( tree.node_type_downcast_ref::<String>( children[ 1 ] ).unwrap().unwrap(), "Multiply" );
+    assert_eq!( tree.children_len( children[ 1 ] ).unwrap(), 1, "Replacement keeps its child." );
+    let result = tree.replace_child( 4, 3, Tree::new() );
+    assert!( matches!( result, Err( TreeError::ExceedsChildren( 3, 4 ) ) ), "Expected ExceedsChildren error." );
+}
+
+#[test]
+fn common_prefix_length() {
+    let tree = expression_tree();
+    assert_eq!( tree.common_prefix_length( 3, 8 ).unwrap(), 2, "Leaves z and 2 share Statement and Equal." );
+    assert_eq!( tree.common_prefix_length( 6, 7 ).unwrap(), 4, "Leaves x and y share Add." );
+    assert_eq!( tree.common_prefix_length( 6, 6 ).unwrap(), 5, "Same node." );
+    assert_eq!( tree.common_prefix_length( 6, 4 ).unwrap(), 3, "Divide is an ancestor of leaf x." );
+    assert!( tree.common_prefix_length( 6, 9 ).is_err(), "Node 9 does not exist." );
+}
+
+#[test]
+fn insert_sorted() {
+    let mut tree = expression_tree();
+    let alphabetical = |data: &Vec<Box<dyn Any>>, child: usize, tree: &Tree| {
+        let value = data[ 0 ].downcast_ref::<String>().unwrap();
+        value.cmp( tree.data_get_ref( child, 0 ).unwrap().downcast_ref::<String>().unwrap() )
+    };
+    let add = tree.insert( 2, ALLOW_CHILDREN, Some( Box::new( "Add".to_string() ) ), None ).unwrap();
+    for value in [ "m", "c", "x", "a", "m" ] {
+        let data: Vec<Box<dyn Any>> = vec![ Box::new( value.to_string() ) ];
+        tree.insert_sorted( add, ALLOW_DATA, None, None, data, alphabetical ).unwrap();
+    }
+    let values = tree
+        .children( add )
+        .unwrap()
+        .iter()
+        .map( |&child| tree.data_get_ref( child, 0 ).unwrap().downcast_ref::<String>().unwrap().as_str() )
+        .collect::<Vec<&str>>();
+    assert_eq!( values, vec![ "a", "c", "m", "m", "x" ], "Children are in alphabetical order." );
+    assert_eq!( tree.children( add ).unwrap()[ 2 ], 10, "First m is before the second m." );
+}
+
+#[test]
+fn trim_empty_branches() {
+    let mut tree = expression_tree();
+    for index in [ 3, 8 ] {
+        tree.data_mut( index ).unwrap().clear();
+    }
+    let group = tree.insert( 1, ALLOW_CHILDREN, None, None ).unwrap();
+    tree.insert( group, ALLOW_DATA, None, None ).unwrap();
+    assert_eq!( tree.trim_empty_branches().unwrap(), 4, "Deleted leaves z and 2, and the empty group." );
+    assert_eq!( tree.count(), 7, "7 nodes are present." );
+    assert_eq!( tree.children( 1 ).unwrap(), &vec![ 2 ], "Empty group was deleted." );
+    assert_eq!( tree.children( 2 ).unwrap(), &vec![ 4 ], "Leaf z was deleted." );
+    assert_eq!( tree.children( 4 ).unwrap(), &vec![ 5 ], "Leaf 2 was deleted." );
+    assert_eq!( tree.children( 5 ).unwrap(), &vec![ 6, 7 ], "Branch with data is kept entirely." );
+    tree.data_mut( 6 ).unwrap().clear();
+    tree.data_mut( 7 ).unwrap().clear();
+    assert_eq!( tree.trim_empty_branches().unwrap(), 6, "Everything but the root was deleted." );
+    assert_eq!( tree.count(), 1, "Root is never deleted." );
+}
+
+#[test]
+fn forest_summary() {
+    let mut tree = expression_tree();
+    assert_eq!( tree.forest_summary(), vec![ ( 0, 9 ) ], "Single component of all the nodes." );
+    tree.split_off( 5 ).unwrap();
+    let summary = tree.forest_summary();
+    assert_eq!( summary, vec![ ( 0, 6 ) ], "Split off nodes are not counted." );
+    assert_eq!( summary.iter().map( |( _, count )| count ).sum::<usize>(), tree.count(), "Sums to the node count." );
+    assert!( Tree::new().forest_summary().is_empty(), "Empty tree has no components." );
+}
+
+#[test]
+fn reparent_if() {
+    let mut tree = expression_tree();
+    let operators_only = |_: usize, new_parent: usize, tree: &Tree| {
+        tree.node_type_downcast_ref::<String>( new_parent ).unwrap().map_or( false, |node_type| {
+            node_type == "Add" || node_type == "Divide"
+        } )
+    };
+    let result = tree.reparent_if( 3, 1, Position::Back, operators_only );
+    assert!( matches!( result, Err( TreeError::MoveRejected( 3, 1 ) ) ), "Expected MoveRejected error." );
+    assert_eq!( tree.parent( 3 ).unwrap(), 2, "Leaf z was not moved." );
+    tree.reparent_if( 3, 5, Position::Front, operators_only ).unwrap();
+    assert_eq!( tree.children( 5 ).unwrap(), &vec![ 3, 6, 7 ], "Leaf z moved into Add." );
+    let result = tree.reparent_if( 4, 5, Position::Back, |_, _, _| true );
+    assert!( matches!( result, Err( TreeError::IsAncestorOf( 5, 4 ) ) ), "Expected IsAncestorOf error." );
+}
+
+#[test]
+fn search_pruned() {
+    let tree = expression_tree();
+    let mut visited = Vec::<usize>::new();
+    let found = tree.search_pruned( 0, |index, tree| {
+        visited.push( index );
+        match tree.node_type_downcast_ref::<String>( index ).unwrap().unwrap().as_str() {
+            "Add" => Visit::Skip,
+            "Leaf" => {
+                if tree.data_ref( index ).unwrap()[ 0 ].downcast_ref::<String>().unwrap() == "2" {
+                    Visit::Found
+                } else {
+                    Visit::Descend
+                }
+            },
+            _ => Visit::Descend,
+        }
+    } );
+    assert_eq!( found, Some( 8 ), "Found leaf 2." );
+    assert_eq!( visited, vec![ 0, 1, 2, 3, 4, 5, 8 ], "Nodes visited." );
+    assert!( !visited.contains( &6 ), "Leaf x within Add was never visited." );
+    assert_eq!( tree.search_pruned( 5, |_, _| Visit::Skip ), None, "Nothing found." );
+    assert_eq!( tree.search_pruned( 20, |_, _| Visit::Found ), None, "Invalid start." );
+}
+
+#[test]
+fn reserve_for() {
+    let mut tree = expression_tree();
+    tree.delete( 8 ).unwrap();
+    let length = tree.len();
+    tree.reserve_for( 200 );
+    let capacity = tree.capacity();
+    assert!( capacity >= length + 199, "Capacity grew by the reserved amount less the hole." );
+    let mut other = Tree::new();
+    other.insert( 0, ALLOW_CHILDREN, Some( Box::new( "Add".to_string() ) ), None ).unwrap();
+    for _ in 0..199 {
+        other.insert( 0, ALLOW_DATA, Some( Box::new( "Leaf".to_string() ) ), None ).unwrap();
+    }
+    tree.graft_many( 4, vec![ other ] ).unwrap();
+    assert_eq!( tree.count(), 208, "All grafted nodes are present." );
+    assert_eq!( tree.capacity(), capacity, "Graft caused no further reallocation." );
+
+    // `graft_many` reserves for the incoming trees itself.
+    let mut tree = expression_tree();
+    let mut other = Tree::new();
+    other.insert( 0, ALLOW_CHILDREN, None, None ).unwrap();
+    for _ in 0..99 {
+        other.insert( 0, ALLOW_DATA, None, None ).unwrap();
+    }
+    tree.graft_many( 4, vec![ other ] ).unwrap();
+    assert!( tree.capacity() >= 109, "Capacity covers the grafted nodes." );
+}
+
+#[test]
+fn children_iter() {
+    let tree = expression_tree();
+    for index in [ 0, 1, 2, 4, 5 ] {
+        assert_eq!(
+            &tree.children_iter( index ).unwrap().collect::<Vec<usize>>(),
+            tree.children( index ).unwrap(),
+            "Same children as children()."
+        );
+    }
+    let leaves = tree.children_iter( 4 ).unwrap()
+        .filter( |&index| tree.data_ref( index ).is_ok() )
+        .collect::<Vec<usize>>();
+    assert_eq!( leaves, vec![ 8 ], "Leaf children of Divide." );
+    let result = tree.children_iter( 3 );
+    assert!( matches!( result, Err( TreeError::NoChildrenAllowed( 3 ) ) ), "Expected NoChildrenAllowed error." );
+}
+
+#[test]
+fn position_of_data() {
+    let mut tree = expression_tree();
+    let data = tree.data_mut( 6 ).unwrap();
+    data.push( Box::new( 5i32 ) );
+    data.push( Box::new( "target".to_string() ) );
+    data.push( Box::new( "other".to_string() ) );
+    let is_target = |data: &Box<dyn Any>| data.downcast_ref::<String>().map_or( false, |value| value == "target" );
+    assert_eq!( tree.position_of_data( 6, is_target ).unwrap(), Some( 2 ), "Found target." );
+    assert_eq!( tree.position_of_data( 7, is_target ).unwrap(), None, "No target in leaf y." );
+    let position = tree.position_of_data( 6, is_target ).unwrap().unwrap();
+    tree.data_mut( 6 ).unwrap().remove( position );
+    assert_eq!( tree.data_ref( 6 ).unwrap().len(), 3, "Target removed." );
+    let result = tree.position_of_data( 4, is_target );
+    assert!( matches!( result, Err( TreeError::NoDataAllowed( 4 ) ) ), "Expected NoDataAllowed error." );
+}
+
+#[test]
+fn swap_children() {
+    let mut tree = expression_tree();
+    tree.insert( 4, ALLOW_DATA, Some( Box::new( "Leaf".to_string() ) ), None ).unwrap();
+    assert_eq!( tree.children( 4 ).unwrap(), &vec![ 5, 8, 9 ], "Divide has three children." );
+    tree.swap_children( 4, 0, 2 ).unwrap();
+    assert_eq!( tree.children( 4 ).unwrap(), &vec![ 9, 8, 5 ], "First and last swapped." );
+    assert_eq!( tree.parent( 5 ).unwrap(), 4, "Parent unchanged." );
+    assert_eq!( tree.parent( 9 ).unwrap(), 4, "Parent unchanged." );
+    let result = tree.swap_children( 4, 1, 3 );
+    assert!( matches!( result, Err( TreeError::ExceedsChildren( 3, 4 ) ) ), "Expected ExceedsChildren error." );
+    assert_eq!( tree.children( 4 ).unwrap(), &vec![ 9, 8, 5 ], "Order unchanged after error." );
+    let result = tree.swap_children( 3, 0, 0 );
+    assert!( matches!( result, Err( TreeError::NoChildrenAllowed( 3 ) ) ), "Expected NoChildrenAllowed error." );
+}
+
+#[test]
+fn cursor() {
+    let tree = expression_tree();
+    let root = tree.cursor( 0 ).unwrap();
+    assert_eq!( root.index(), 0, "At root." );
+    assert!( root.parent().is_none(), "Root has no parent." );
+    assert!( root.next_sibling().is_none(), "Root has no sibling." );
+    let equal = root.first_child().unwrap().first_child().unwrap();
+    assert_eq!( equal.index(), 2, "At Equal." );
+    let z = equal.first_child().unwrap();
+    assert_eq!( z.index(), 3, "At leaf z." );
+    assert!( z.first_child().is_none(), "Leaf has no children." );
+    let divide = z.next_sibling().unwrap();
+    assert_eq!( divide.index(), 4, "At Divide." );
+    assert!( divide.next_sibling().is_none(), "Divide is the last child." );
+    assert_eq!( divide.parent().unwrap().index(), 2, "Back at Equal." );
+    let children = divide.children().iter().map( |cursor| cursor.index() ).collect::<Vec<usize>>();
+    assert_eq!( children, vec![ 5, 8 ], "Children of Divide." );
+    assert!( z.children().is_empty(), "Leaf has no children." );
+    assert!( tree.cursor( 20 ).is_err(), "Invalid node." );
+}
+
+#[test]
+fn set_on_mutation() {
+    let events = Rc::new( RefCell::new( Vec::<Mutation>::new() ) );
+    let mut tree = Tree::new();
+    let recorder = Rc::clone( &events );
+    tree.set_on_mutation( move |mutation| recorder.borrow_mut().push( mutation ) );
+    tree.insert( 0, ALLOW_CHILDREN, Some( Box::new( "Root".to_string() ) ), None ).unwrap();
+    tree.insert( 0, ALLOW_CHILDREN, Some( Box::new( "Statement".to_string() ) ), None ).unwrap();
+    tree.insert( 1, ALLOW_DATA, Some( Box::new( "Leaf".to_string() ) ), None ).unwrap();
+    tree.insert_at( 1, 0, ALLOW_DATA, Some( Box::new( "Leaf".to_string() ) ), None ).unwrap();
+    assert!( tree.insert( 2, ALLOW_DATA, None, None ).is_err(), "Leaf can't have children." );
+    tree.delete( 2 ).unwrap();
+    assert_eq!(
+        *events.borrow(),
+        vec![
+            Mutation::Insert( 0 ),
+            Mutation::Insert( 1 ),
+            Mutation::Insert( 2 ),
+            Mutation::Insert( 3 ),
+            Mutation::Delete( 2 ),
+        ],
+        "Inserts and delete were notified, but not the failed insert."
+    );
+    events.borrow_mut().clear();
+    tree.data_mut( 3 ).unwrap().push( Box::new( "x".to_string() ) );
+    tree.move_nodes( 3, 0, None ).unwrap();
+    tree.take( 3 ).unwrap();
+    assert_eq!(
+        *events.borrow(),
+        vec![ Mutation::Data( 3 ), Mutation::Move( 3 ), Mutation::Delete( 3 ) ],
+        "Data change, move and take were notified."
+    );
+}
+
+#[test]
+fn set_on_mutation_split_off_clear() {
+    let events = Rc::new( RefCell::new( Vec::<Mutation>::new() ) );
+    let mut tree = Tree::new();
+    tree.insert( 0, ALLOW_CHILDREN, None, None ).unwrap();
+    tree.insert( 0, ALLOW_CHILDREN, None, None ).unwrap();
+    tree.insert( 1, ALLOW_DATA, None, None ).unwrap();
+    tree.insert( 1, ALLOW_DATA, None, None ).unwrap();
+    tree.insert( 0, ALLOW_CHILDREN | ALLOW_DATA, None, None ).unwrap();
+    let recorder = Rc::clone( &events );
+    tree.set_on_mutation( move |mutation| recorder.borrow_mut().push( mutation ) );
+    tree.swap_children( 1, 0, 1 ).unwrap();
+    tree.set_features( 4, ALLOW_DATA ).unwrap();
+    assert_eq!(
+        *events.borrow(),
+        vec![ Mutation::Children( 1 ), Mutation::Update( 4 ) ],
+        "Swapped children and changed features were notified."
+    );
+    events.borrow_mut().clear();
+    let split = tree.split_off( 1 ).unwrap();
+    assert_eq!( split.count(), 3, "Subtree of 3 nodes split off." );
+    assert_eq!(
+        *events.borrow(),
+        vec![ Mutation::Delete( 1 ), Mutation::Delete( 3 ), Mutation::Delete( 2 ) ],
+        "Every node split off was notified."
+    );
+    events.borrow_mut().clear();
+    tree.clear();
+    assert_eq!(
+        *events.borrow(),
+        vec![ Mutation::Delete( 0 ), Mutation::Delete( 4 ) ],
+        "Every cleared node was notified."
+    );
+}
+
+#[test]
+fn to_shape() {
+    let leaf = || TreeShape::Node { features: ALLOW_DATA, children: Vec::new() };
+    let branch = |children: Vec<TreeShape>| TreeShape::Node { features: ALLOW_CHILDREN, children };
+    let tree = expression_tree();
+    assert_eq!(
+        tree.to_shape().unwrap(),
+        branch( vec![
+            branch( vec![
+                branch( vec![
+                    leaf(),
+                    branch( vec![
+                        branch( vec![ leaf(), leaf() ] ),
+                        leaf(),
+                    ] ),
+                ] ),
+            ] ),
+        ] ),
+        "Shape of the expression tree."
+    );
+    assert_eq!( Tree::new().to_shape(), None, "Empty tree has no shape." );
+}
+
+#[test]
+fn to_shape_deep_chain() {
+    let mut tree = Tree::new();
+    let mut last = tree.insert( 0, ALLOW_CHILDREN, None, None ).unwrap();
+    for _ in 0..20_000 {
+        last = tree.insert( last, ALLOW_CHILDREN, None, None ).unwrap();
+    }
+    let shape = tree.to_shape().unwrap();
+    let mut level = &shape;
+    let mut depth = 0;
+    loop {
+        let TreeShape::Node { children, .. } = level;
+        assert!( children.len() <= 1, "Every node of the chain has at most 1 child." );
+        let Some( child ) = children.first() else {
+            break;
+        };
+        level = child;
+        depth += 1;
+    }
+    assert_eq!( depth, 20_000, "Shape has the depth of the chain." );
+}
+
+#[test]
+fn from_shape() {
+    let shape = TreeShape::Node {
+        features: ALLOW_CHILDREN,
+        children: vec![
+            TreeShape::Node {
+                features: ALLOW_CHILDREN | ALLOW_DATA,
+                children: vec![ TreeShape::Node { features: ALLOW_DATA, children: Vec::new() } ],
+            },
+            TreeShape::Node { features: ALLOW_CHILDREN, children: Vec::new() },
+            TreeShape::Node { features: ALLOW_DATA, children: Vec::new() },
+        ],
+    };
+    let tree = Tree::from_shape( &shape );
+    assert_eq!( tree.count(), 5, "5 nodes are present." );
+    assert_eq!( tree.children( 0 ).unwrap(), &vec![ 1, 3, 4 ], "Children of root in pre-order." );
+    assert_eq!( tree.children( 1 ).unwrap(), &vec![ 2 ], "Children of node 1." );
+    assert!( tree.data_ref( 1 ).unwrap().is_empty(), "Empty data." );
+    assert!( tree.data_ref( 3 ).is_err(), "Node 3 has no data." );
+    assert_eq!( tree.to_shape().unwrap(), shape, "Round-tripped shape." );
+    let expression = expression_tree().to_shape().unwrap();
+    assert_eq!( Tree::from_shape( &expression ).to_shape().unwrap(), expression, "Round-tripped expression tree." );
+}
+
+#[test]
+fn iter_data_typed() {
+    let mut tree = expression_tree();
+    tree.data_mut( 6 ).unwrap().push( Box::new( "w".to_string() ) );
+    let items = tree.iter_data_typed::<String>( 6 ).unwrap().collect::<Vec<Option<&String>>>();
+    assert_eq!( items.len(), 2, "2 data items." );
+    assert!( items.iter().all( |item| item.is_some() ), "All data items are strings." );
+    assert_eq!( items[ 0 ].unwrap(), "x", "First data item." );
+    assert_eq!( items[ 1 ].unwrap(), "w", "Second data item." );
+    assert!( tree.iter_data_typed::<i32>( 6 ).unwrap().all( |item| item.is_none() ), "No data item is an i32." );
+    assert!( tree.iter_data_typed::<String>( 4 ).is_err(), "Divide has no data." );
+}